@@ -1,4 +1,8 @@
-use std::{cell::RefCell, collections::VecDeque, rc::Rc};
+use std::{
+    cell::RefCell,
+    collections::{BTreeMap, VecDeque},
+    rc::Rc,
+};
 
 pub struct Node {
     pub value: i32,
@@ -6,6 +10,8 @@ pub struct Node {
     pub right: Option<Rc<RefCell<Node>>>,
 }
 
+bt_node::impl_tree_node!(Node);
+
 impl Node {
     pub fn new(value: i32) -> Rc<RefCell<Node>> {
         Rc::new(RefCell::new(Node {
@@ -16,26 +22,36 @@ impl Node {
     }
 }
 
-pub fn order_traversal(root: Rc<RefCell<Node>>) -> Vec<Vec<i32>> {
-    let mut result: Vec<Vec<i32>> = Vec::new();
+pub fn order_traversal(root: Option<Rc<RefCell<Node>>>) -> Vec<Vec<i32>> {
+    levels(root)
+        .iter()
+        .map(|level| level.iter().map(|node| node.borrow().value).collect())
+        .collect()
+}
+
+// Groups the nodes level by level, left to right.
+fn levels(root: Option<Rc<RefCell<Node>>>) -> Vec<Vec<Rc<RefCell<Node>>>> {
+    let mut result = Vec::new();
     let mut queue = VecDeque::new();
-    queue.push_back(root);
+    if let Some(root) = root {
+        queue.push_back(root);
+    }
 
     while !queue.is_empty() {
         let mut new_queue = VecDeque::new();
         let mut sub_result = Vec::new();
-        while !queue.is_empty() {
-            let node = queue.pop_front().unwrap();
-            let borrowed = node.borrow();
-            sub_result.push(borrowed.value);
+        while let Some(node) = queue.pop_front() {
+            {
+                let borrowed = node.borrow();
+                if let Some(ref left) = borrowed.left {
+                    new_queue.push_back(left.clone());
+                }
 
-            if let Some(ref left) = borrowed.left {
-                new_queue.push_back(left.clone());
-            }
-
-            if let Some(ref right) = borrowed.right {
-                new_queue.push_back(right.clone());
+                if let Some(ref right) = borrowed.right {
+                    new_queue.push_back(right.clone());
+                }
             }
+            sub_result.push(node);
         }
 
         queue = new_queue;
@@ -45,6 +61,155 @@ pub fn order_traversal(root: Rc<RefCell<Node>>) -> Vec<Vec<i32>> {
     result
 }
 
+/// Level order where every other level is read right to left, starting
+/// with the root level left to right.
+pub fn zigzag_order(root: Option<Rc<RefCell<Node>>>) -> Vec<Vec<i32>> {
+    let mut result = order_traversal(root);
+    for level in result.iter_mut().skip(1).step_by(2) {
+        level.reverse();
+    }
+    result
+}
+
+/// Level order from the deepest level up to the root.
+pub fn bottom_up_order(root: Option<Rc<RefCell<Node>>>) -> Vec<Vec<i32>> {
+    let mut result = order_traversal(root);
+    result.reverse();
+    result
+}
+
+/// Groups values by column, from the leftmost column to the rightmost. The
+/// root is column 0, a left child is one column left of its parent and a
+/// right child one column right. Values in a column keep level order, and
+/// left to right within a level.
+pub fn vertical_order(root: Option<Rc<RefCell<Node>>>) -> Vec<Vec<i32>> {
+    let mut columns: BTreeMap<i64, Vec<i32>> = BTreeMap::new();
+    let mut queue = VecDeque::new();
+    if let Some(root) = root {
+        queue.push_back((root, 0));
+    }
+
+    while let Some((node, column)) = queue.pop_front() {
+        let borrowed = node.borrow();
+        columns.entry(column).or_default().push(borrowed.value);
+
+        if let Some(ref left) = borrowed.left {
+            queue.push_back((left.clone(), column - 1));
+        }
+
+        if let Some(ref right) = borrowed.right {
+            queue.push_back((right.clone(), column + 1));
+        }
+    }
+
+    columns.into_values().collect()
+}
+
+/// Anti-clockwise boundary starting at the root: the left boundary top-down,
+/// then every leaf left to right, then the right boundary bottom-up. Each
+/// node appears once.
+pub fn boundary_traversal(root: Option<Rc<RefCell<Node>>>) -> Vec<i32> {
+    let root = match root {
+        Some(root) => root,
+        None => return Vec::new(),
+    };
+
+    let borrowed = root.borrow();
+    let mut result = vec![borrowed.value];
+    if is_leaf(&borrowed) {
+        return result;
+    }
+
+    let mut cur = borrowed.left.clone();
+    while let Some(node) = cur {
+        let borrowed = node.borrow();
+        if !is_leaf(&borrowed) {
+            result.push(borrowed.value);
+        }
+        cur = borrowed.left.clone().or_else(|| borrowed.right.clone());
+    }
+
+    collect_leaves(borrowed.left.clone(), &mut result);
+    collect_leaves(borrowed.right.clone(), &mut result);
+
+    let mut right_boundary = Vec::new();
+    let mut cur = borrowed.right.clone();
+    while let Some(node) = cur {
+        let borrowed = node.borrow();
+        if !is_leaf(&borrowed) {
+            right_boundary.push(borrowed.value);
+        }
+        cur = borrowed.right.clone().or_else(|| borrowed.left.clone());
+    }
+    result.extend(right_boundary.into_iter().rev());
+
+    result
+}
+
+fn is_leaf(node: &Node) -> bool {
+    node.left.is_none() && node.right.is_none()
+}
+
+fn collect_leaves(node: Option<Rc<RefCell<Node>>>, result: &mut Vec<i32>) {
+    let node = match node {
+        Some(node) => node,
+        None => return,
+    };
+
+    let borrowed = node.borrow();
+    if is_leaf(&borrowed) {
+        result.push(borrowed.value);
+        return;
+    }
+
+    collect_leaves(borrowed.left.clone(), result);
+    collect_leaves(borrowed.right.clone(), result);
+}
+
+/// The first value of every level, as seen from the left.
+pub fn left_side_view(root: Option<Rc<RefCell<Node>>>) -> Vec<i32> {
+    levels(root)
+        .iter()
+        .filter_map(|level| level.first().map(|node| node.borrow().value))
+        .collect()
+}
+
+/// The last value of every level, as seen from the right.
+pub fn right_side_view(root: Option<Rc<RefCell<Node>>>) -> Vec<i32> {
+    levels(root)
+        .iter()
+        .filter_map(|level| level.last().map(|node| node.borrow().value))
+        .collect()
+}
+
+/// Sum of every level. Summed as `i64` so wide levels cannot overflow.
+pub fn level_sums(root: Option<Rc<RefCell<Node>>>) -> Vec<i64> {
+    order_traversal(root)
+        .iter()
+        .map(|level| level.iter().map(|&value| value as i64).sum())
+        .collect()
+}
+
+/// Mean of every level, taken over an `i64` sum so wide levels cannot
+/// overflow.
+pub fn level_averages(root: Option<Rc<RefCell<Node>>>) -> Vec<f64> {
+    order_traversal(root)
+        .iter()
+        .map(|level| {
+            let sum: i64 = level.iter().map(|&value| value as i64).sum();
+            sum as f64 / level.len() as f64
+        })
+        .collect()
+}
+
+/// Largest value of every level.
+pub fn level_maxes(root: Option<Rc<RefCell<Node>>>) -> Vec<i32> {
+    order_traversal(root)
+        .iter()
+        .filter_map(|level| level.iter().max().copied())
+        .collect()
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -52,7 +217,7 @@ mod test {
     #[test]
     fn test_single_node() {
         let root = Node::new(1);
-        let result = order_traversal(root);
+        let result = order_traversal(Some(root));
         assert_eq!(result, vec![vec![1]]);
     }
 
@@ -62,7 +227,7 @@ mod test {
         root.borrow_mut().left = Some(Node::new(2));
         root.borrow_mut().right = Some(Node::new(3));
 
-        let result = order_traversal(root);
+        let result = order_traversal(Some(root));
         assert_eq!(result, vec![vec![1], vec![2, 3]]);
     }
 
@@ -80,7 +245,7 @@ mod test {
         right.borrow_mut().left = Some(Node::new(6));
         right.borrow_mut().right = Some(Node::new(7));
 
-        let result = order_traversal(root);
+        let result = order_traversal(Some(root));
         assert_eq!(result, vec![vec![1], vec![2, 3], vec![4, 5, 6, 7]]);
     }
 
@@ -95,7 +260,7 @@ mod test {
         let left_left = left.borrow().left.clone().unwrap();
         left_left.borrow_mut().left = Some(Node::new(4));
 
-        let result = order_traversal(root);
+        let result = order_traversal(Some(root));
         assert_eq!(result, vec![vec![1], vec![2], vec![3], vec![4]]);
     }
 
@@ -110,7 +275,7 @@ mod test {
         let right_right = right.borrow().right.clone().unwrap();
         right_right.borrow_mut().right = Some(Node::new(4));
 
-        let result = order_traversal(root);
+        let result = order_traversal(Some(root));
         assert_eq!(result, vec![vec![1], vec![2], vec![3], vec![4]]);
     }
 
@@ -127,7 +292,139 @@ mod test {
         let left_right = left.borrow().right.clone().unwrap();
         left_right.borrow_mut().left = Some(Node::new(6));
 
-        let result = order_traversal(root);
+        let result = order_traversal(Some(root));
         assert_eq!(result, vec![vec![1], vec![2, 3], vec![4, 5], vec![6]]);
     }
+
+    #[test]
+    fn test_empty_tree() {
+        assert!(order_traversal(None).is_empty());
+        assert!(zigzag_order(None).is_empty());
+        assert!(bottom_up_order(None).is_empty());
+        assert!(vertical_order(None).is_empty());
+        assert!(boundary_traversal(None).is_empty());
+        assert!(left_side_view(None).is_empty());
+        assert!(right_side_view(None).is_empty());
+        assert!(level_sums(None).is_empty());
+        assert!(level_averages(None).is_empty());
+        assert!(level_maxes(None).is_empty());
+    }
+
+    // Builds:
+    //          1
+    //        /   \
+    //       2     3
+    //      / \     \
+    //     4   5     6
+    //        / \
+    //       7   8
+    fn build_sample() -> Rc<RefCell<Node>> {
+        let root = Node::new(1);
+        root.borrow_mut().left = Some(Node::new(2));
+        root.borrow_mut().right = Some(Node::new(3));
+
+        let left = root.borrow().left.clone().unwrap();
+        left.borrow_mut().left = Some(Node::new(4));
+        left.borrow_mut().right = Some(Node::new(5));
+
+        let right = root.borrow().right.clone().unwrap();
+        right.borrow_mut().right = Some(Node::new(6));
+
+        let left_right = left.borrow().right.clone().unwrap();
+        left_right.borrow_mut().left = Some(Node::new(7));
+        left_right.borrow_mut().right = Some(Node::new(8));
+        root
+    }
+
+    #[test]
+    fn test_zigzag_order() {
+        let result = zigzag_order(Some(build_sample()));
+        assert_eq!(result, vec![vec![1], vec![3, 2], vec![4, 5, 6], vec![8, 7]]);
+    }
+
+    #[test]
+    fn test_bottom_up_order() {
+        let result = bottom_up_order(Some(build_sample()));
+        assert_eq!(result, vec![vec![7, 8], vec![4, 5, 6], vec![2, 3], vec![1]]);
+    }
+
+    #[test]
+    fn test_vertical_order() {
+        // columns: 4 | 2 7 | 1 5 | 3 8 | 6
+        let result = vertical_order(Some(build_sample()));
+        assert_eq!(
+            result,
+            vec![vec![4], vec![2, 7], vec![1, 5], vec![3, 8], vec![6]]
+        );
+    }
+
+    #[test]
+    fn test_vertical_order_single_node() {
+        let result = vertical_order(Some(Node::new(1)));
+        assert_eq!(result, vec![vec![1]]);
+    }
+
+    #[test]
+    fn test_boundary_traversal() {
+        let result = boundary_traversal(Some(build_sample()));
+        assert_eq!(result, vec![1, 2, 4, 7, 8, 6, 3]);
+    }
+
+    #[test]
+    fn test_boundary_traversal_single_node() {
+        let result = boundary_traversal(Some(Node::new(1)));
+        assert_eq!(result, vec![1]);
+    }
+
+    #[test]
+    fn test_boundary_traversal_left_skewed() {
+        let root = Node::new(1);
+        root.borrow_mut().left = Some(Node::new(2));
+
+        let left = root.borrow().left.clone().unwrap();
+        left.borrow_mut().right = Some(Node::new(3));
+
+        let result = boundary_traversal(Some(root));
+        assert_eq!(result, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_side_views() {
+        let root = build_sample();
+        assert_eq!(left_side_view(Some(root.clone())), vec![1, 2, 4, 7]);
+        assert_eq!(right_side_view(Some(root)), vec![1, 3, 6, 8]);
+    }
+
+    #[test]
+    fn test_side_views_hidden_branch() {
+        // the deepest node is on the left, so the right view sees it too
+        let root = Node::new(1);
+        root.borrow_mut().left = Some(Node::new(2));
+        root.borrow_mut().right = Some(Node::new(3));
+
+        let left = root.borrow().left.clone().unwrap();
+        left.borrow_mut().left = Some(Node::new(4));
+
+        assert_eq!(right_side_view(Some(root.clone())), vec![1, 3, 4]);
+        assert_eq!(left_side_view(Some(root)), vec![1, 2, 4]);
+    }
+
+    #[test]
+    fn test_level_aggregates() {
+        let root = build_sample();
+        assert_eq!(level_sums(Some(root.clone())), vec![1, 5, 15, 15]);
+        assert_eq!(level_averages(Some(root.clone())), vec![1.0, 2.5, 5.0, 7.5]);
+        assert_eq!(level_maxes(Some(root)), vec![1, 3, 6, 8]);
+    }
+
+    #[test]
+    fn test_level_sums_do_not_overflow() {
+        let root = Node::new(0);
+        root.borrow_mut().left = Some(Node::new(i32::MAX));
+        root.borrow_mut().right = Some(Node::new(i32::MAX));
+
+        let result = level_sums(Some(root.clone()));
+        assert_eq!(result, vec![0, 2 * i32::MAX as i64]);
+        assert_eq!(level_averages(Some(root)), vec![0.0, i32::MAX as f64]);
+    }
 }
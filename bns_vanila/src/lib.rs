@@ -147,6 +147,6 @@ mod test {
         let idx = binary_search(&nums, target);
         println!("{:?}", idx);
         // Should find any occurrence of 2 (indices 1, 2, or 3 are all valid)
-        assert!(idx >= 1 && idx <= 3);
+        assert!((1..=3).contains(&idx));
     }
 }
@@ -1,4 +1,4 @@
-use std::{cell::RefCell, i32, rc::Rc};
+use std::{cell::RefCell, rc::Rc};
 
 pub struct Node {
     pub value: i32,
@@ -6,6 +6,8 @@ pub struct Node {
     pub right: Option<Rc<RefCell<Node>>>,
 }
 
+bt_node::impl_tree_node!(Node);
+
 impl Node {
     pub fn new(value: i32) -> Rc<RefCell<Node>> {
         let node = Self {
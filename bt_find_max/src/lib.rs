@@ -1,13 +1,27 @@
-use std::{cell::RefCell, rc::Rc};
-
-pub struct Node {
-    pub value: i32,
-    pub left: Option<Rc<RefCell<Node>>>,
-    pub right: Option<Rc<RefCell<Node>>>,
+//! Extremes and top values of binary trees.
+//!
+//! The searches return the winning node rather than `&T`: values sit behind
+//! a `RefCell`, so a reference to one can only live as long as a borrow of
+//! its node. Read the value with [`Node::value`], which borrows it in place
+//! instead of cloning it out.
+
+use std::{
+    cell::{Ref, RefCell},
+    cmp::Reverse,
+    collections::BinaryHeap,
+    rc::Rc,
+};
+
+pub struct Node<T> {
+    pub value: T,
+    pub left: Option<Rc<RefCell<Node<T>>>>,
+    pub right: Option<Rc<RefCell<Node<T>>>>,
 }
 
-impl Node {
-    pub fn new(value: i32) -> Rc<RefCell<Node>> {
+bt_node::impl_tree_node!(Node<T>);
+
+impl<T> Node<T> {
+    pub fn new(value: T) -> Rc<RefCell<Node<T>>> {
         let node = Self {
             value,
             left: None,
@@ -16,36 +30,139 @@ impl Node {
 
         Rc::new(RefCell::new(node))
     }
+
+    /// Borrows the value of `node` for as long as the returned `Ref` lives.
+    pub fn value(node: &Rc<RefCell<Node<T>>>) -> Ref<'_, T> {
+        Ref::map(node.borrow(), |node| &node.value)
+    }
+}
+
+/// Returns the node holding the largest value (argmax).
+///
+/// On ties the node found first in pre-order wins.
+pub fn find_max_node<T: Ord>(node: Option<Rc<RefCell<Node<T>>>>) -> Option<Rc<RefCell<Node<T>>>> {
+    find_extreme_node(node, &|candidate, best| candidate > best)
+}
+
+/// Returns the node holding the smallest value (argmin).
+///
+/// On ties the node found first in pre-order wins.
+pub fn find_min_node<T: Ord>(node: Option<Rc<RefCell<Node<T>>>>) -> Option<Rc<RefCell<Node<T>>>> {
+    find_extreme_node(node, &|candidate, best| candidate < best)
+}
+
+fn find_extreme_node<T, F>(
+    node: Option<Rc<RefCell<Node<T>>>>,
+    better: &F,
+) -> Option<Rc<RefCell<Node<T>>>>
+where
+    F: Fn(&T, &T) -> bool,
+{
+    let node = node?;
+
+    let (left, right) = {
+        let borrowed = node.borrow();
+        (borrowed.left.clone(), borrowed.right.clone())
+    };
+
+    let mut best = node;
+    for child in [left, right] {
+        if let Some(candidate) = find_extreme_node(child, better)
+            && better(&candidate.borrow().value, &best.borrow().value)
+        {
+            best = candidate;
+        }
+    }
+
+    Some(best)
 }
 
-pub fn find_max(node: Option<Rc<RefCell<Node>>>) -> i32 {
+/// Returns the `k` largest values in descending order.
+///
+/// Keeps a min-heap of at most `k` entries, so it runs in `O(n log k)` time
+/// and `O(k)` extra space. Duplicates are counted separately.
+pub fn top_k<T: Ord + Clone>(node: Option<Rc<RefCell<Node<T>>>>, k: usize) -> Vec<T> {
+    let mut heap = BinaryHeap::with_capacity(k + 1);
+    if k > 0 {
+        collect_top_k(node, k, &mut heap);
+    }
+
+    let mut result: Vec<T> = heap.into_iter().map(|Reverse(value)| value).collect();
+    result.sort_by(|a, b| b.cmp(a));
+    result
+}
+
+fn collect_top_k<T: Ord + Clone>(
+    node: Option<Rc<RefCell<Node<T>>>>,
+    k: usize,
+    heap: &mut BinaryHeap<Reverse<T>>,
+) {
     let node = match node {
         Some(node) => node,
-        None => return i32::MIN,
+        None => return,
     };
 
     let borrowed = node.borrow();
-    let left = find_max(borrowed.left.clone());
-    let right = find_max(borrowed.right.clone());
-    return borrowed.value.max(left.max(right));
+    if heap.len() < k {
+        heap.push(Reverse(borrowed.value.clone()));
+    } else if let Some(Reverse(smallest)) = heap.peek()
+        && borrowed.value > *smallest
+    {
+        heap.pop();
+        heap.push(Reverse(borrowed.value.clone()));
+    }
+
+    collect_top_k(borrowed.left.clone(), k, heap);
+    collect_top_k(borrowed.right.clone(), k, heap);
+}
+
+/// Returns the node holding the largest value of a binary search tree,
+/// found in `O(height)` by walking the rightmost spine. The result is
+/// meaningless if the tree does not satisfy the BST ordering.
+pub fn bst_max_node<T>(node: Option<Rc<RefCell<Node<T>>>>) -> Option<Rc<RefCell<Node<T>>>> {
+    let mut cur = node?;
+    loop {
+        let next = cur.borrow().right.clone();
+        match next {
+            Some(next) => cur = next,
+            None => return Some(cur),
+        }
+    }
+}
+
+/// Returns the node holding the smallest value of a binary search tree,
+/// found in `O(height)` by walking the leftmost spine.
+pub fn bst_min_node<T>(node: Option<Rc<RefCell<Node<T>>>>) -> Option<Rc<RefCell<Node<T>>>> {
+    let mut cur = node?;
+    loop {
+        let next = cur.borrow().left.clone();
+        match next {
+            Some(next) => cur = next,
+            None => return Some(cur),
+        }
+    }
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
 
+    fn value_of<T: Copy>(node: Option<Rc<RefCell<Node<T>>>>) -> Option<T> {
+        node.as_ref().map(Node::value).as_deref().copied()
+    }
+
     #[test]
     fn test_single_node() {
         let root = Node::new(5);
-        let result = find_max(Some(root));
-        assert_eq!(result, 5);
+        let result = value_of(find_max_node(Some(root)));
+        assert_eq!(result, Some(5));
     }
 
     #[test]
     fn test_single_node_negative() {
         let root = Node::new(-10);
-        let result = find_max(Some(root));
-        assert_eq!(result, -10);
+        let result = value_of(find_max_node(Some(root)));
+        assert_eq!(result, Some(-10));
     }
 
     #[test]
@@ -54,8 +171,8 @@ mod test {
         root.borrow_mut().left = Some(Node::new(5));
         root.borrow_mut().right = Some(Node::new(3));
 
-        let result = find_max(Some(root));
-        assert_eq!(result, 10);
+        let result = value_of(find_max_node(Some(root)));
+        assert_eq!(result, Some(10));
     }
 
     #[test]
@@ -64,8 +181,8 @@ mod test {
         root.borrow_mut().left = Some(Node::new(15));
         root.borrow_mut().right = Some(Node::new(3));
 
-        let result = find_max(Some(root));
-        assert_eq!(result, 15);
+        let result = value_of(find_max_node(Some(root)));
+        assert_eq!(result, Some(15));
     }
 
     #[test]
@@ -74,8 +191,8 @@ mod test {
         root.borrow_mut().left = Some(Node::new(3));
         root.borrow_mut().right = Some(Node::new(20));
 
-        let result = find_max(Some(root));
-        assert_eq!(result, 20);
+        let result = value_of(find_max_node(Some(root)));
+        assert_eq!(result, Some(20));
     }
 
     #[test]
@@ -88,8 +205,8 @@ mod test {
         left.borrow_mut().left = Some(Node::new(2));
         left.borrow_mut().right = Some(Node::new(50));
 
-        let result = find_max(Some(root));
-        assert_eq!(result, 50);
+        let result = value_of(find_max_node(Some(root)));
+        assert_eq!(result, Some(50));
     }
 
     #[test]
@@ -102,8 +219,8 @@ mod test {
         right.borrow_mut().left = Some(Node::new(12));
         right.borrow_mut().right = Some(Node::new(100));
 
-        let result = find_max(Some(root));
-        assert_eq!(result, 100);
+        let result = value_of(find_max_node(Some(root)));
+        assert_eq!(result, Some(100));
     }
 
     #[test]
@@ -116,8 +233,8 @@ mod test {
         left.borrow_mut().left = Some(Node::new(-20));
         left.borrow_mut().right = Some(Node::new(-15));
 
-        let result = find_max(Some(root));
-        assert_eq!(result, -3);
+        let result = value_of(find_max_node(Some(root)));
+        assert_eq!(result, Some(-3));
     }
 
     #[test]
@@ -130,8 +247,8 @@ mod test {
         left.borrow_mut().left = Some(Node::new(-20));
         left.borrow_mut().right = Some(Node::new(15));
 
-        let result = find_max(Some(root));
-        assert_eq!(result, 15);
+        let result = value_of(find_max_node(Some(root)));
+        assert_eq!(result, Some(15));
     }
 
     #[test]
@@ -145,8 +262,8 @@ mod test {
         let left_left = left.borrow().left.clone().unwrap();
         left_left.borrow_mut().left = Some(Node::new(40));
 
-        let result = find_max(Some(root));
-        assert_eq!(result, 40);
+        let result = value_of(find_max_node(Some(root)));
+        assert_eq!(result, Some(40));
     }
 
     #[test]
@@ -160,8 +277,8 @@ mod test {
         let right_right = right.borrow().right.clone().unwrap();
         right_right.borrow_mut().right = Some(Node::new(50));
 
-        let result = find_max(Some(root));
-        assert_eq!(result, 50);
+        let result = value_of(find_max_node(Some(root)));
+        assert_eq!(result, Some(50));
     }
 
     #[test]
@@ -178,8 +295,8 @@ mod test {
         right.borrow_mut().left = Some(Node::new(28));
         right.borrow_mut().right = Some(Node::new(35));
 
-        let result = find_max(Some(root));
-        assert_eq!(result, 35);
+        let result = value_of(find_max_node(Some(root)));
+        assert_eq!(result, Some(35));
     }
 
     #[test]
@@ -188,7 +305,204 @@ mod test {
         root.borrow_mut().left = Some(Node::new(-5));
         root.borrow_mut().right = Some(Node::new(-10));
 
-        let result = find_max(Some(root));
-        assert_eq!(result, 0);
+        let result = value_of(find_max_node(Some(root)));
+        assert_eq!(result, Some(0));
+    }
+
+    #[test]
+    fn test_empty_tree() {
+        assert!(find_max_node::<i32>(None).is_none());
+        assert!(find_min_node::<i32>(None).is_none());
+        assert!(bst_max_node::<i32>(None).is_none());
+        assert!(top_k::<i32>(None, 3).is_empty());
+    }
+
+    #[test]
+    fn test_min_value_is_not_empty() {
+        let root = Node::new(i32::MIN);
+        let result = value_of(find_max_node(Some(root)));
+        assert_eq!(result, Some(i32::MIN));
+    }
+
+    #[test]
+    fn test_find_min() {
+        let root = Node::new(10);
+        root.borrow_mut().left = Some(Node::new(5));
+        root.borrow_mut().right = Some(Node::new(15));
+
+        let right = root.borrow().right.clone().unwrap();
+        right.borrow_mut().left = Some(Node::new(-7));
+
+        let result = value_of(find_min_node(Some(root)));
+        assert_eq!(result, Some(-7));
+    }
+
+    #[test]
+    fn test_generic_values() {
+        let root = Node::new("mango");
+        root.borrow_mut().left = Some(Node::new("apple"));
+        root.borrow_mut().right = Some(Node::new("pear"));
+
+        assert_eq!(value_of(find_max_node(Some(root.clone()))), Some("pear"));
+        assert_eq!(value_of(find_min_node(Some(root))), Some("apple"));
+    }
+
+    #[test]
+    fn test_values_are_borrowed_not_cloned() {
+        #[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+        struct Score(u32);
+
+        let root = Node::new(Score(4));
+        root.borrow_mut().left = Some(Node::new(Score(9)));
+        root.borrow_mut().right = Some(Node::new(Score(1)));
+
+        let max = find_max_node(Some(root.clone())).unwrap();
+        let min = find_min_node(Some(root)).unwrap();
+        assert_eq!(*Node::value(&max), Score(9));
+        assert_eq!(*Node::value(&min), Score(1));
+
+        //    4
+        //   / \
+        //  1   9
+        let bst = Node::new(Score(4));
+        bst.borrow_mut().left = Some(Node::new(Score(1)));
+        bst.borrow_mut().right = Some(Node::new(Score(9)));
+
+        let max = bst_max_node(Some(bst.clone())).unwrap();
+        let min = bst_min_node(Some(bst)).unwrap();
+        assert_eq!(*Node::value(&max), Score(9));
+        assert_eq!(*Node::value(&min), Score(1));
+    }
+
+    #[test]
+    fn test_find_max_node_returns_handle() {
+        let root = Node::new(10);
+        let target = Node::new(42);
+        root.borrow_mut().left = Some(Node::new(5));
+        root.borrow_mut().right = Some(target.clone());
+
+        let result = find_max_node(Some(root)).unwrap();
+        assert!(Rc::ptr_eq(&result, &target));
+
+        // the handle can be used to mutate the tree in place
+        result.borrow_mut().value = 0;
+        assert_eq!(target.borrow().value, 0);
+    }
+
+    #[test]
+    fn test_find_min_node_returns_handle() {
+        let root = Node::new(10);
+        let target = Node::new(1);
+        root.borrow_mut().left = Some(Node::new(5));
+
+        let left = root.borrow().left.clone().unwrap();
+        left.borrow_mut().right = Some(target.clone());
+
+        let result = find_min_node(Some(root)).unwrap();
+        assert!(Rc::ptr_eq(&result, &target));
+    }
+
+    #[test]
+    fn test_find_max_node_tie_prefers_preorder() {
+        let root = Node::new(1);
+        let first = Node::new(9);
+        let second = Node::new(9);
+        root.borrow_mut().left = Some(first.clone());
+        root.borrow_mut().right = Some(second);
+
+        let result = find_max_node(Some(root)).unwrap();
+        assert!(Rc::ptr_eq(&result, &first));
+    }
+
+    #[test]
+    fn test_top_k() {
+        let root = Node::new(25);
+        root.borrow_mut().left = Some(Node::new(15));
+        root.borrow_mut().right = Some(Node::new(30));
+
+        let left = root.borrow().left.clone().unwrap();
+        left.borrow_mut().left = Some(Node::new(10));
+        left.borrow_mut().right = Some(Node::new(20));
+
+        let right = root.borrow().right.clone().unwrap();
+        right.borrow_mut().left = Some(Node::new(28));
+        right.borrow_mut().right = Some(Node::new(35));
+
+        assert_eq!(top_k(Some(root.clone()), 3), vec![35, 30, 28]);
+        assert_eq!(top_k(Some(root.clone()), 1), vec![35]);
+        assert!(top_k(Some(root), 0).is_empty());
+    }
+
+    #[test]
+    fn test_top_k_larger_than_tree() {
+        let root = Node::new(2);
+        root.borrow_mut().left = Some(Node::new(7));
+        root.borrow_mut().right = Some(Node::new(2));
+
+        let result = top_k(Some(root), 10);
+        assert_eq!(result, vec![7, 2, 2]);
+    }
+
+    #[test]
+    fn test_bst_min_max() {
+        //        8
+        //       / \
+        //      3   10
+        //     / \    \
+        //    1   6    14
+        let root = Node::new(8);
+        root.borrow_mut().left = Some(Node::new(3));
+        root.borrow_mut().right = Some(Node::new(10));
+
+        let left = root.borrow().left.clone().unwrap();
+        left.borrow_mut().left = Some(Node::new(1));
+        left.borrow_mut().right = Some(Node::new(6));
+
+        let right = root.borrow().right.clone().unwrap();
+        right.borrow_mut().right = Some(Node::new(14));
+
+        assert_eq!(value_of(bst_max_node(Some(root.clone()))), Some(14));
+        assert_eq!(value_of(bst_min_node(Some(root.clone()))), Some(1));
+        assert_eq!(
+            value_of(bst_max_node(Some(root.clone()))),
+            value_of(find_max_node(Some(root.clone())))
+        );
+        assert_eq!(
+            value_of(bst_min_node(Some(root.clone()))),
+            value_of(find_min_node(Some(root)))
+        );
+    }
+
+    #[test]
+    fn test_bst_node_handles() {
+        let root = Node::new(8);
+        let smallest = Node::new(3);
+        root.borrow_mut().left = Some(smallest.clone());
+
+        assert!(Rc::ptr_eq(
+            &bst_min_node(Some(root.clone())).unwrap(),
+            &smallest
+        ));
+        assert!(Rc::ptr_eq(
+            &bst_max_node(Some(root.clone())).unwrap(),
+            &root
+        ));
+        assert!(bst_max_node::<i32>(None).is_none());
+    }
+
+    #[test]
+    fn test_deep_clone_generic() {
+        let root = Node::new(String::from("b"));
+        root.borrow_mut().left = Some(Node::new(String::from("a")));
+
+        let copy = Node::deep_clone(&root);
+        assert_eq!(copy, root);
+
+        let left = copy.borrow().left.clone().unwrap();
+        left.borrow_mut().value.push('z');
+        let max = find_max_node(Some(copy)).unwrap();
+        let min = find_min_node(Some(root)).unwrap();
+        assert_eq!(*Node::value(&max), "b");
+        assert_eq!(*Node::value(&min), "a");
     }
 }
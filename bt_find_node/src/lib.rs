@@ -6,6 +6,8 @@ pub struct Node {
     pub right: Option<Rc<RefCell<Node>>>,
 }
 
+bt_node::impl_tree_node!(Node);
+
 impl Node {
     pub fn new(value: i32) -> Rc<RefCell<Node>> {
         let node = Self {
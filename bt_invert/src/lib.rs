@@ -1,11 +1,15 @@
 use std::{cell::RefCell, rc::Rc};
 
+pub mod transforms;
+
 pub struct Node {
     pub value: i32,
     pub left: Option<Rc<RefCell<Node>>>,
     pub right: Option<Rc<RefCell<Node>>>,
 }
 
+bt_node::impl_tree_node!(Node);
+
 impl Node {
     pub fn new(value: i32) -> Rc<RefCell<Node>> {
         let node = Self {
@@ -19,20 +23,23 @@ impl Node {
 }
 
 pub fn invert(node: Option<Rc<RefCell<Node>>>) {
-    let node = match node {
-        Some(node) => node,
-        None => return,
-    };
+    if let Some(node) = node {
+        invert_node(&node);
+    }
+}
 
-    // borrow & post-order
+fn invert_node(node: &Rc<RefCell<Node>>) {
     let mut borrowed = node.borrow_mut();
-    invert(borrowed.left.clone());
-    invert(borrowed.right.clone());
+    let borrowed = &mut *borrowed;
 
-    // invert
-    let tmp = borrowed.left.clone();
-    borrowed.left = borrowed.right.clone();
-    borrowed.right = tmp;
+    // swap the handles in place, then recurse into the children
+    std::mem::swap(&mut borrowed.left, &mut borrowed.right);
+    if let Some(left) = &borrowed.left {
+        invert_node(left);
+    }
+    if let Some(right) = &borrowed.right {
+        invert_node(right);
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,548 @@
+use std::{
+    cell::RefCell,
+    fmt,
+    rc::{Rc, Weak},
+};
+
+use crate::Node;
+
+/// Returns an inverted copy of the tree and leaves the original untouched.
+pub fn mirror(root: Option<Rc<RefCell<Node>>>) -> Option<Rc<RefCell<Node>>> {
+    let root = root?;
+    let borrowed = root.borrow();
+
+    let copy = Node::new(borrowed.value);
+    {
+        let mut copy_borrowed = copy.borrow_mut();
+        copy_borrowed.left = mirror(borrowed.right.clone());
+        copy_borrowed.right = mirror(borrowed.left.clone());
+    }
+
+    Some(copy)
+}
+
+/// Flattens the tree in place into a right-leaning chain in pre-order.
+/// Every `left` pointer is cleared.
+pub fn flatten(root: Option<Rc<RefCell<Node>>>) {
+    if let Some(root) = root {
+        flatten_node(root);
+    }
+}
+
+// Flattens the subtree and returns its last node.
+fn flatten_node(node: Rc<RefCell<Node>>) -> Rc<RefCell<Node>> {
+    let (left, right) = {
+        let mut borrowed = node.borrow_mut();
+        (borrowed.left.take(), borrowed.right.take())
+    };
+
+    let mut tail = node;
+    for child in [left, right].into_iter().flatten() {
+        tail.borrow_mut().right = Some(child.clone());
+        tail = flatten_node(child);
+    }
+
+    tail
+}
+
+/// A node of a [`DoublyLinkedList`]. The next pointer owns the following
+/// node and the previous pointer is `Weak`, so the list has no reference
+/// cycles.
+pub struct ListNode {
+    pub value: i32,
+    prev: Option<Weak<RefCell<ListNode>>>,
+    next: Option<Rc<RefCell<ListNode>>>,
+}
+
+impl ListNode {
+    pub fn prev(&self) -> Option<Rc<RefCell<ListNode>>> {
+        self.prev.as_ref().and_then(Weak::upgrade)
+    }
+
+    pub fn next(&self) -> Option<Rc<RefCell<ListNode>>> {
+        self.next.clone()
+    }
+
+    fn neighbour_values(&self) -> (Option<i32>, Option<i32>) {
+        let value = |node: Rc<RefCell<ListNode>>| node.borrow().value;
+        (self.prev().map(value), self.next().map(value))
+    }
+}
+
+// Comparison and `Debug` look at the neighbours' values only, so neither
+// walks the rest of the list.
+impl PartialEq for ListNode {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value && self.neighbour_values() == other.neighbour_values()
+    }
+}
+
+impl Eq for ListNode {}
+
+impl fmt::Debug for ListNode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (prev, next) = self.neighbour_values();
+        f.debug_struct("ListNode")
+            .field("value", &self.value)
+            .field("prev", &prev)
+            .field("next", &next)
+            .finish()
+    }
+}
+
+/// A sorted doubly linked list built from the values of a binary search
+/// tree.
+#[derive(Default)]
+pub struct DoublyLinkedList {
+    head: Option<Rc<RefCell<ListNode>>>,
+    tail: Option<Rc<RefCell<ListNode>>>,
+}
+
+impl DoublyLinkedList {
+    /// The node with the smallest value.
+    pub fn head(&self) -> Option<Rc<RefCell<ListNode>>> {
+        self.head.clone()
+    }
+
+    /// The node with the largest value.
+    pub fn tail(&self) -> Option<Rc<RefCell<ListNode>>> {
+        self.tail.clone()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.head.is_none()
+    }
+
+    /// Values from head to tail.
+    pub fn values(&self) -> Vec<i32> {
+        let mut values = Vec::new();
+        let mut cur = self.head();
+        while let Some(node) = cur {
+            let borrowed = node.borrow();
+            values.push(borrowed.value);
+            cur = borrowed.next();
+        }
+        values
+    }
+
+    fn push_back(&mut self, value: i32) {
+        let node = Rc::new(RefCell::new(ListNode {
+            value,
+            prev: self.tail.as_ref().map(Rc::downgrade),
+            next: None,
+        }));
+        match self.tail.replace(node.clone()) {
+            Some(tail) => tail.borrow_mut().next = Some(node),
+            None => self.head = Some(node),
+        }
+    }
+}
+
+impl Drop for DoublyLinkedList {
+    // Unlinks the nodes one by one so a long list is not dropped
+    // recursively. Stops at a node a caller still holds, which keeps the
+    // rest alive.
+    fn drop(&mut self) {
+        self.tail = None;
+        let mut cur = self.head.take();
+        while let Some(node) = cur {
+            cur = match Rc::try_unwrap(node) {
+                Ok(node) => node.into_inner().next.take(),
+                Err(_) => None,
+            };
+        }
+    }
+}
+
+impl fmt::Debug for DoublyLinkedList {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.values()).finish()
+    }
+}
+
+/// Collects the values of a binary search tree into a sorted
+/// [`DoublyLinkedList`], leaving the tree untouched.
+pub fn bst_to_doubly_linked_list(root: Option<Rc<RefCell<Node>>>) -> DoublyLinkedList {
+    let mut list = DoublyLinkedList::default();
+    push_inorder(root, &mut list);
+    list
+}
+
+fn push_inorder(node: Option<Rc<RefCell<Node>>>, list: &mut DoublyLinkedList) {
+    let node = match node {
+        Some(node) => node,
+        None => return,
+    };
+
+    let borrowed = node.borrow();
+    push_inorder(borrowed.left.clone(), list);
+    list.push_back(borrowed.value);
+    push_inorder(borrowed.right.clone(), list);
+}
+
+/// Removes every subtree whose root value satisfies `predicate` and returns
+/// the new root, which is `None` if the root itself was pruned.
+pub fn prune<F>(root: Option<Rc<RefCell<Node>>>, predicate: F) -> Option<Rc<RefCell<Node>>>
+where
+    F: Fn(i32) -> bool,
+{
+    prune_node(root, &predicate)
+}
+
+fn prune_node<F>(node: Option<Rc<RefCell<Node>>>, predicate: &F) -> Option<Rc<RefCell<Node>>>
+where
+    F: Fn(i32) -> bool,
+{
+    let node = node?;
+    if predicate(node.borrow().value) {
+        return None;
+    }
+
+    let (left, right) = {
+        let mut borrowed = node.borrow_mut();
+        (borrowed.left.take(), borrowed.right.take())
+    };
+
+    let left = prune_node(left, predicate);
+    let right = prune_node(right, predicate);
+    {
+        let mut borrowed = node.borrow_mut();
+        borrowed.left = left;
+        borrowed.right = right;
+    }
+
+    Some(node)
+}
+
+/// Trims a binary search tree in place so only values in `[lo, hi]` remain,
+/// keeping the BST ordering. Returns the new root.
+pub fn trim_bst(root: Option<Rc<RefCell<Node>>>, lo: i32, hi: i32) -> Option<Rc<RefCell<Node>>> {
+    let node = root?;
+    let value = node.borrow().value;
+
+    // the whole left (or right) side is out of range, skip the node
+    if value < lo {
+        let right = node.borrow_mut().right.take();
+        return trim_bst(right, lo, hi);
+    }
+    if value > hi {
+        let left = node.borrow_mut().left.take();
+        return trim_bst(left, lo, hi);
+    }
+
+    let (left, right) = {
+        let mut borrowed = node.borrow_mut();
+        (borrowed.left.take(), borrowed.right.take())
+    };
+
+    let left = trim_bst(left, lo, hi);
+    let right = trim_bst(right, lo, hi);
+    {
+        let mut borrowed = node.borrow_mut();
+        borrowed.left = left;
+        borrowed.right = right;
+    }
+
+    Some(node)
+}
+
+/// Replaces every value with `f(value)` in place, visiting nodes in
+/// pre-order.
+pub fn map_values<F>(root: Option<Rc<RefCell<Node>>>, mut f: F)
+where
+    F: FnMut(i32) -> i32,
+{
+    if let Some(root) = root {
+        map_node(&root, &mut f);
+    }
+}
+
+fn map_node<F>(node: &Rc<RefCell<Node>>, f: &mut F)
+where
+    F: FnMut(i32) -> i32,
+{
+    let mut borrowed = node.borrow_mut();
+    borrowed.value = f(borrowed.value);
+
+    if let Some(left) = &borrowed.left {
+        map_node(left, f);
+    }
+    if let Some(right) = &borrowed.right {
+        map_node(right, f);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::invert;
+
+    // Builds:
+    //        4
+    //       / \
+    //      2   6
+    //     / \   \
+    //    1   3   7
+    fn build_bst() -> Rc<RefCell<Node>> {
+        let root = Node::new(4);
+        root.borrow_mut().left = Some(Node::new(2));
+        root.borrow_mut().right = Some(Node::new(6));
+
+        let left = root.borrow().left.clone().unwrap();
+        left.borrow_mut().left = Some(Node::new(1));
+        left.borrow_mut().right = Some(Node::new(3));
+
+        let right = root.borrow().right.clone().unwrap();
+        right.borrow_mut().right = Some(Node::new(7));
+        root
+    }
+
+    fn right_chain(node: Option<Rc<RefCell<Node>>>) -> Vec<i32> {
+        let mut result = Vec::new();
+        let mut cur = node;
+        while let Some(node) = cur {
+            let borrowed = node.borrow();
+            result.push(borrowed.value);
+            cur = borrowed.right.clone();
+        }
+        result
+    }
+
+    #[test]
+    fn test_mirror_empty_tree() {
+        assert!(mirror(None).is_none());
+    }
+
+    #[test]
+    fn test_mirror_matches_invert() {
+        let root = build_bst();
+        let mirrored = mirror(Some(root.clone())).unwrap();
+
+        // original is untouched
+        assert_eq!(root, build_bst());
+
+        let inverted = build_bst();
+        invert(Some(inverted.clone()));
+        assert_eq!(mirrored, inverted);
+    }
+
+    #[test]
+    fn test_mirror_is_a_copy() {
+        let root = build_bst();
+        let mirrored = mirror(Some(root.clone())).unwrap();
+        mirrored.borrow_mut().value = 100;
+
+        assert_eq!(root.borrow().value, 4);
+    }
+
+    #[test]
+    fn test_flatten() {
+        let root = build_bst();
+        flatten(Some(root.clone()));
+
+        assert_eq!(right_chain(Some(root.clone())), vec![4, 2, 1, 3, 6, 7]);
+
+        let mut cur = Some(root);
+        while let Some(node) = cur {
+            assert!(node.borrow().left.is_none());
+            cur = node.borrow().right.clone();
+        }
+    }
+
+    #[test]
+    fn test_flatten_single_node_and_empty() {
+        flatten(None);
+
+        let root = Node::new(1);
+        flatten(Some(root.clone()));
+        assert_eq!(*root.borrow(), *Node::new(1).borrow());
+    }
+
+    #[test]
+    fn test_flatten_left_skewed_tree() {
+        let root = Node::new(1);
+        root.borrow_mut().left = Some(Node::new(2));
+        let left = root.borrow().left.clone().unwrap();
+        left.borrow_mut().left = Some(Node::new(3));
+
+        flatten(Some(root.clone()));
+        assert_eq!(right_chain(Some(root)), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_bst_to_doubly_linked_list() {
+        let root = build_bst();
+        let list = bst_to_doubly_linked_list(Some(root.clone()));
+        assert_eq!(list.values(), vec![1, 2, 3, 4, 6, 7]);
+        assert_eq!(root, build_bst());
+
+        // walk back from the tail through the previous pointers
+        let mut backwards = Vec::new();
+        let mut cur = list.tail();
+        while let Some(node) = cur {
+            backwards.push(node.borrow().value);
+            cur = node.borrow().prev();
+        }
+        assert_eq!(backwards, vec![7, 6, 4, 3, 2, 1]);
+
+        assert!(list.head().unwrap().borrow().prev().is_none());
+        assert!(list.tail().unwrap().borrow().next().is_none());
+    }
+
+    #[test]
+    fn test_list_nodes_format_and_compare() {
+        let list = bst_to_doubly_linked_list(Some(build_bst()));
+        let other = bst_to_doubly_linked_list(Some(build_bst()));
+        let head = list.head().unwrap();
+        let second = head.borrow().next().unwrap();
+
+        assert_eq!(
+            format!("{:?}", second.borrow()),
+            "ListNode { value: 2, prev: Some(1), next: Some(3) }"
+        );
+        assert_eq!(
+            format!("{:?}", head.borrow()),
+            "ListNode { value: 1, prev: None, next: Some(2) }"
+        );
+        assert_eq!(head, other.head().unwrap());
+        assert_ne!(head, second);
+        assert_eq!(second.borrow().prev(), Some(head.clone()));
+    }
+
+    #[test]
+    fn test_doubly_linked_list_drop_frees_nodes() {
+        let list = bst_to_doubly_linked_list(Some(build_bst()));
+        let head = Rc::downgrade(&list.head().unwrap());
+        let tail = Rc::downgrade(&list.tail().unwrap());
+
+        drop(list);
+        assert!(head.upgrade().is_none());
+        assert!(tail.upgrade().is_none());
+    }
+
+    #[test]
+    fn test_held_node_outlives_list() {
+        let list = bst_to_doubly_linked_list(Some(build_bst()));
+        let middle = list.head().unwrap().borrow().next().unwrap();
+        let head = Rc::downgrade(&list.head().unwrap());
+
+        drop(list);
+        // the nodes before `middle` are gone, the ones after stay reachable
+        assert!(head.upgrade().is_none());
+        assert!(middle.borrow().prev().is_none());
+        assert_eq!(middle.borrow().next().unwrap().borrow().value, 3);
+    }
+
+    #[test]
+    fn test_long_list_drops_without_recursing() {
+        let mut list = DoublyLinkedList::default();
+        for value in 0..1_000_000 {
+            list.push_back(value);
+        }
+        drop(list);
+    }
+
+    #[test]
+    fn test_doubly_linked_list_debug() {
+        let list = bst_to_doubly_linked_list(Some(build_bst()));
+        assert_eq!(format!("{:?}", list), "[1, 2, 3, 4, 6, 7]");
+    }
+
+    #[test]
+    fn test_bst_to_doubly_linked_list_empty() {
+        let list = bst_to_doubly_linked_list(None);
+        assert!(list.is_empty());
+        assert!(list.tail().is_none());
+        assert_eq!(format!("{:?}", list), "[]");
+    }
+
+    #[test]
+    fn test_prune() {
+        let root = prune(Some(build_bst()), |value| value == 2).unwrap();
+
+        let expected = Node::new(4);
+        expected.borrow_mut().right = Some(Node::new(6));
+        let right = expected.borrow().right.clone().unwrap();
+        right.borrow_mut().right = Some(Node::new(7));
+
+        assert_eq!(root, expected);
+    }
+
+    #[test]
+    fn test_prune_root() {
+        assert!(prune(Some(build_bst()), |value| value == 4).is_none());
+        assert!(prune(None, |_| true).is_none());
+    }
+
+    #[test]
+    fn test_prune_nothing_matches() {
+        let root = prune(Some(build_bst()), |value| value > 100).unwrap();
+        assert_eq!(root, build_bst());
+    }
+
+    #[test]
+    fn test_trim_bst() {
+        let root = trim_bst(Some(build_bst()), 2, 6).unwrap();
+
+        //      4
+        //     / \
+        //    2   6
+        //     \
+        //      3
+        let expected = Node::new(4);
+        expected.borrow_mut().left = Some(Node::new(2));
+        expected.borrow_mut().right = Some(Node::new(6));
+        let left = expected.borrow().left.clone().unwrap();
+        left.borrow_mut().right = Some(Node::new(3));
+
+        assert_eq!(root, expected);
+    }
+
+    #[test]
+    fn test_trim_bst_replaces_root() {
+        let root = trim_bst(Some(build_bst()), 5, 10).unwrap();
+
+        let expected = Node::new(6);
+        expected.borrow_mut().right = Some(Node::new(7));
+        assert_eq!(root, expected);
+    }
+
+    #[test]
+    fn test_trim_bst_out_of_range() {
+        assert!(trim_bst(Some(build_bst()), 10, 20).is_none());
+        assert!(trim_bst(None, 0, 1).is_none());
+    }
+
+    #[test]
+    fn test_map_values() {
+        let root = build_bst();
+        map_values(Some(root.clone()), |value| value * 10);
+
+        assert_eq!(root.borrow().value, 40);
+        assert_eq!(root.borrow().left.as_ref().unwrap().borrow().value, 20);
+        assert_eq!(
+            root.borrow()
+                .right
+                .as_ref()
+                .unwrap()
+                .borrow()
+                .right
+                .as_ref()
+                .unwrap()
+                .borrow()
+                .value,
+            70
+        );
+    }
+
+    #[test]
+    fn test_map_values_preorder() {
+        let mut visited = Vec::new();
+        map_values(Some(build_bst()), |value| {
+            visited.push(value);
+            value
+        });
+
+        assert_eq!(visited, vec![4, 2, 1, 3, 6, 7]);
+    }
+}
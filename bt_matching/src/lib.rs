@@ -1,4 +1,9 @@
-use std::{cell::RefCell, rc::Rc};
+use std::{
+    cell::RefCell,
+    collections::hash_map::DefaultHasher,
+    hash::{Hash, Hasher},
+    rc::Rc,
+};
 
 pub struct Node {
     pub value: i32,
@@ -6,6 +11,8 @@ pub struct Node {
     pub right: Option<Rc<RefCell<Node>>>,
 }
 
+bt_node::impl_tree_node!(Node);
+
 impl Node {
     pub fn new(value: i32) -> Rc<RefCell<Node>> {
         let node = Self {
@@ -38,6 +45,209 @@ pub fn matching(a: Option<Rc<RefCell<Node>>>, b: Option<Rc<RefCell<Node>>>) -> b
     left && right
 }
 
+/// Returns true when `b` is the mirror image of `a`.
+pub fn is_mirror(a: Option<Rc<RefCell<Node>>>, b: Option<Rc<RefCell<Node>>>) -> bool {
+    let (a, b) = match (a, b) {
+        (None, None) => return true,
+        (Some(a), Some(b)) => (a, b),
+        _ => return false,
+    };
+
+    let borrowed_a = a.borrow();
+    let borrowed_b = b.borrow();
+    if borrowed_a.value != borrowed_b.value {
+        return false;
+    }
+
+    is_mirror(borrowed_a.left.clone(), borrowed_b.right.clone())
+        && is_mirror(borrowed_a.right.clone(), borrowed_b.left.clone())
+}
+
+/// Returns true when the tree is symmetric around its root.
+pub fn is_symmetric(root: Option<Rc<RefCell<Node>>>) -> bool {
+    match root {
+        Some(root) => {
+            let borrowed = root.borrow();
+            is_mirror(borrowed.left.clone(), borrowed.right.clone())
+        }
+        None => true,
+    }
+}
+
+/// Returns true when `a` can be turned into `b` by swapping the children of
+/// any number of nodes.
+pub fn is_isomorphic(a: Option<Rc<RefCell<Node>>>, b: Option<Rc<RefCell<Node>>>) -> bool {
+    let (a, b) = match (a, b) {
+        (None, None) => return true,
+        (Some(a), Some(b)) => (a, b),
+        _ => return false,
+    };
+
+    let borrowed_a = a.borrow();
+    let borrowed_b = b.borrow();
+    if borrowed_a.value != borrowed_b.value {
+        return false;
+    }
+
+    let straight = is_isomorphic(borrowed_a.left.clone(), borrowed_b.left.clone())
+        && is_isomorphic(borrowed_a.right.clone(), borrowed_b.right.clone());
+    straight
+        || is_isomorphic(borrowed_a.left.clone(), borrowed_b.right.clone())
+            && is_isomorphic(borrowed_a.right.clone(), borrowed_b.left.clone())
+}
+
+/// Returns true when both trees have the same shape, ignoring values.
+pub fn same_shape(a: Option<Rc<RefCell<Node>>>, b: Option<Rc<RefCell<Node>>>) -> bool {
+    let (a, b) = match (a, b) {
+        (None, None) => return true,
+        (Some(a), Some(b)) => (a, b),
+        _ => return false,
+    };
+
+    let borrowed_a = a.borrow();
+    let borrowed_b = b.borrow();
+    same_shape(borrowed_a.left.clone(), borrowed_b.left.clone())
+        && same_shape(borrowed_a.right.clone(), borrowed_b.right.clone())
+}
+
+/// Returns true when `small` matches some subtree of `big` exactly.
+///
+/// Compares `small` against every node of `big`, so it runs in `O(n * m)`.
+/// An empty `small` is a subtree of every tree.
+pub fn is_subtree(big: Option<Rc<RefCell<Node>>>, small: Option<Rc<RefCell<Node>>>) -> bool {
+    if small.is_none() {
+        return true;
+    }
+
+    let big = match big {
+        Some(big) => big,
+        None => return false,
+    };
+
+    if matching(Some(big.clone()), small.clone()) {
+        return true;
+    }
+
+    let borrowed = big.borrow();
+    is_subtree(borrowed.left.clone(), small.clone()) || is_subtree(borrowed.right.clone(), small)
+}
+
+/// Same as [`is_subtree`] but in expected `O(n + m)`.
+///
+/// Every node of `big` gets a structural hash computed bottom-up from its
+/// value and its children's hashes. Only nodes whose hash equals the hash of
+/// `small` are compared with [`matching`], which rules out false positives
+/// from hash collisions.
+pub fn is_subtree_hashed(big: Option<Rc<RefCell<Node>>>, small: Option<Rc<RefCell<Node>>>) -> bool {
+    if small.is_none() {
+        return true;
+    }
+
+    let target = structural_hash(small.clone(), &mut |_, _| {});
+    let mut found = false;
+    structural_hash(big, &mut |node, hash| {
+        if !found && hash == target && matching(Some(node.clone()), small.clone()) {
+            found = true;
+        }
+    });
+    found
+}
+
+// Post-order structural hash. `visit` is called with every node and its hash.
+fn structural_hash<F>(node: Option<Rc<RefCell<Node>>>, visit: &mut F) -> u64
+where
+    F: FnMut(&Rc<RefCell<Node>>, u64),
+{
+    let mut hasher = DefaultHasher::new();
+    let node = match node {
+        Some(node) => node,
+        None => {
+            // distinct marker so a missing child never hashes like a value
+            None::<i32>.hash(&mut hasher);
+            return hasher.finish();
+        }
+    };
+
+    let (left, right) = {
+        let borrowed = node.borrow();
+        (borrowed.left.clone(), borrowed.right.clone())
+    };
+
+    let left = structural_hash(left, visit);
+    let right = structural_hash(right, visit);
+
+    Some(node.borrow().value).hash(&mut hasher);
+    left.hash(&mut hasher);
+    right.hash(&mut hasher);
+    let hash = hasher.finish();
+
+    visit(&node, hash);
+    hash
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    Left,
+    Right,
+}
+
+/// The first place two trees differ.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Difference {
+    /// Path from the root to the differing position.
+    pub path: Vec<Direction>,
+    /// Value in the first tree, `None` if the node is missing there.
+    pub a: Option<i32>,
+    /// Value in the second tree, `None` if the node is missing there.
+    pub b: Option<i32>,
+}
+
+/// Returns the first position, in pre-order, where `a` and `b` differ, or
+/// `None` when the trees match.
+pub fn diff(a: Option<Rc<RefCell<Node>>>, b: Option<Rc<RefCell<Node>>>) -> Option<Difference> {
+    let mut path = Vec::new();
+    diff_at(a, b, &mut path)
+}
+
+fn diff_at(
+    a: Option<Rc<RefCell<Node>>>,
+    b: Option<Rc<RefCell<Node>>>,
+    path: &mut Vec<Direction>,
+) -> Option<Difference> {
+    let (a, b) = match (a, b) {
+        (None, None) => return None,
+        (Some(a), Some(b)) => (a, b),
+        (a, b) => {
+            return Some(Difference {
+                path: path.clone(),
+                a: a.map(|node| node.borrow().value),
+                b: b.map(|node| node.borrow().value),
+            });
+        }
+    };
+
+    let borrowed_a = a.borrow();
+    let borrowed_b = b.borrow();
+    if borrowed_a.value != borrowed_b.value {
+        return Some(Difference {
+            path: path.clone(),
+            a: Some(borrowed_a.value),
+            b: Some(borrowed_b.value),
+        });
+    }
+
+    path.push(Direction::Left);
+    if let Some(difference) = diff_at(borrowed_a.left.clone(), borrowed_b.left.clone(), path) {
+        return Some(difference);
+    }
+    path.pop();
+
+    path.push(Direction::Right);
+    let difference = diff_at(borrowed_a.right.clone(), borrowed_b.right.clone(), path);
+    path.pop();
+    difference
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -45,21 +255,21 @@ mod test {
     #[test]
     fn test_both_empty() {
         let result = matching(None, None);
-        assert_eq!(result, true);
+        assert!(result);
     }
 
     #[test]
     fn test_first_empty_second_not() {
         let b = Node::new(1);
         let result = matching(None, Some(b));
-        assert_eq!(result, false);
+        assert!(!result);
     }
 
     #[test]
     fn test_second_empty_first_not() {
         let a = Node::new(1);
         let result = matching(Some(a), None);
-        assert_eq!(result, false);
+        assert!(!result);
     }
 
     #[test]
@@ -67,7 +277,7 @@ mod test {
         let a = Node::new(5);
         let b = Node::new(5);
         let result = matching(Some(a), Some(b));
-        assert_eq!(result, true);
+        assert!(result);
     }
 
     #[test]
@@ -75,7 +285,7 @@ mod test {
         let a = Node::new(5);
         let b = Node::new(10);
         let result = matching(Some(a), Some(b));
-        assert_eq!(result, false);
+        assert!(!result);
     }
 
     #[test]
@@ -89,7 +299,7 @@ mod test {
         b.borrow_mut().right = Some(Node::new(3));
 
         let result = matching(Some(a), Some(b));
-        assert_eq!(result, true);
+        assert!(result);
     }
 
     #[test]
@@ -103,7 +313,7 @@ mod test {
         b.borrow_mut().right = Some(Node::new(3));
 
         let result = matching(Some(a), Some(b));
-        assert_eq!(result, false);
+        assert!(!result);
     }
 
     #[test]
@@ -117,7 +327,7 @@ mod test {
         b.borrow_mut().right = Some(Node::new(3));
 
         let result = matching(Some(a), Some(b));
-        assert_eq!(result, false);
+        assert!(!result);
     }
 
     #[test]
@@ -131,7 +341,7 @@ mod test {
         b.borrow_mut().right = Some(Node::new(7));
 
         let result = matching(Some(a), Some(b));
-        assert_eq!(result, false);
+        assert!(!result);
     }
 
     #[test]
@@ -142,7 +352,7 @@ mod test {
         let b = Node::new(1);
 
         let result = matching(Some(a), Some(b));
-        assert_eq!(result, false);
+        assert!(!result);
     }
 
     #[test]
@@ -153,7 +363,7 @@ mod test {
         let b = Node::new(1);
 
         let result = matching(Some(a), Some(b));
-        assert_eq!(result, false);
+        assert!(!result);
     }
 
     #[test]
@@ -183,7 +393,7 @@ mod test {
         b_right.borrow_mut().right = Some(Node::new(7));
 
         let result = matching(Some(a), Some(b));
-        assert_eq!(result, true);
+        assert!(result);
     }
 
     #[test]
@@ -205,7 +415,7 @@ mod test {
         b_left.borrow_mut().right = Some(Node::new(99));
 
         let result = matching(Some(a), Some(b));
-        assert_eq!(result, false);
+        assert!(!result);
     }
 
     #[test]
@@ -229,7 +439,7 @@ mod test {
         b_left_left.borrow_mut().left = Some(Node::new(4));
 
         let result = matching(Some(a), Some(b));
-        assert_eq!(result, true);
+        assert!(result);
     }
 
     #[test]
@@ -247,7 +457,7 @@ mod test {
         b_right.borrow_mut().right = Some(Node::new(3));
 
         let result = matching(Some(a), Some(b));
-        assert_eq!(result, true);
+        assert!(result);
     }
 
     #[test]
@@ -261,6 +471,246 @@ mod test {
         b.borrow_mut().right = Some(Node::new(-3));
 
         let result = matching(Some(a), Some(b));
-        assert_eq!(result, true);
+        assert!(result);
+    }
+
+    // Builds:
+    //        1
+    //       / \
+    //      2   3
+    //     / \
+    //    4   5
+    fn build_sample() -> Rc<RefCell<Node>> {
+        let root = Node::new(1);
+        root.borrow_mut().left = Some(Node::new(2));
+        root.borrow_mut().right = Some(Node::new(3));
+
+        let left = root.borrow().left.clone().unwrap();
+        left.borrow_mut().left = Some(Node::new(4));
+        left.borrow_mut().right = Some(Node::new(5));
+        root
+    }
+
+    // Mirror of `build_sample`.
+    fn build_sample_mirror() -> Rc<RefCell<Node>> {
+        let root = Node::new(1);
+        root.borrow_mut().left = Some(Node::new(3));
+        root.borrow_mut().right = Some(Node::new(2));
+
+        let right = root.borrow().right.clone().unwrap();
+        right.borrow_mut().left = Some(Node::new(5));
+        right.borrow_mut().right = Some(Node::new(4));
+        root
+    }
+
+    #[test]
+    fn test_is_mirror() {
+        assert!(is_mirror(None, None));
+        assert!(is_mirror(Some(build_sample()), Some(build_sample_mirror())));
+        assert!(!is_mirror(Some(build_sample()), Some(build_sample())));
+        assert!(!is_mirror(Some(build_sample()), None));
+    }
+
+    #[test]
+    fn test_is_symmetric() {
+        //      1
+        //     / \
+        //    2   2
+        //   /     \
+        //  3       3
+        let root = Node::new(1);
+        root.borrow_mut().left = Some(Node::new(2));
+        root.borrow_mut().right = Some(Node::new(2));
+
+        let left = root.borrow().left.clone().unwrap();
+        left.borrow_mut().left = Some(Node::new(3));
+        let right = root.borrow().right.clone().unwrap();
+        right.borrow_mut().right = Some(Node::new(3));
+
+        assert!(is_symmetric(None));
+        assert!(is_symmetric(Some(root.clone())));
+
+        right.borrow_mut().right = None;
+        right.borrow_mut().left = Some(Node::new(3));
+        assert!(!is_symmetric(Some(root)));
+    }
+
+    #[test]
+    fn test_is_isomorphic() {
+        assert!(is_isomorphic(None, None));
+        assert!(is_isomorphic(Some(build_sample()), Some(build_sample())));
+        assert!(is_isomorphic(
+            Some(build_sample()),
+            Some(build_sample_mirror())
+        ));
+
+        // swap only the root's children
+        let partial = build_sample();
+        let left = partial.borrow_mut().left.take();
+        let right = partial.borrow_mut().right.take();
+        partial.borrow_mut().left = right;
+        partial.borrow_mut().right = left;
+        assert!(is_isomorphic(Some(build_sample()), Some(partial)));
+    }
+
+    #[test]
+    fn test_is_isomorphic_different_values() {
+        let other = build_sample_mirror();
+        let right = other.borrow().right.clone().unwrap();
+        right.borrow_mut().value = 9;
+        assert!(!is_isomorphic(Some(build_sample()), Some(other)));
+    }
+
+    #[test]
+    fn test_same_shape() {
+        let a = build_sample();
+        let b = build_sample();
+        let left = b.borrow().left.clone().unwrap();
+        left.borrow_mut().value = 100;
+        b.borrow_mut().value = -1;
+
+        assert!(same_shape(Some(a.clone()), Some(b)));
+        assert!(!same_shape(Some(a), Some(build_sample_mirror())));
+        assert!(same_shape(None, None));
+    }
+
+    #[test]
+    fn test_is_subtree() {
+        let small = Node::new(2);
+        small.borrow_mut().left = Some(Node::new(4));
+        small.borrow_mut().right = Some(Node::new(5));
+
+        assert!(is_subtree(Some(build_sample()), Some(small.clone())));
+        assert!(is_subtree_hashed(Some(build_sample()), Some(small)));
+    }
+
+    #[test]
+    fn test_is_subtree_requires_whole_subtree() {
+        // 2 -> 4 only exists as a prefix of 2 -> (4, 5)
+        let small = Node::new(2);
+        small.borrow_mut().left = Some(Node::new(4));
+
+        assert!(!is_subtree(Some(build_sample()), Some(small.clone())));
+        assert!(!is_subtree_hashed(Some(build_sample()), Some(small)));
+    }
+
+    #[test]
+    fn test_is_subtree_leaf_and_empty() {
+        let leaf = Node::new(3);
+        assert!(is_subtree(Some(build_sample()), Some(leaf.clone())));
+        assert!(is_subtree_hashed(Some(build_sample()), Some(leaf.clone())));
+
+        assert!(is_subtree(Some(build_sample()), None));
+        assert!(is_subtree_hashed(None, None));
+        assert!(!is_subtree(None, Some(leaf.clone())));
+        assert!(!is_subtree_hashed(None, Some(leaf)));
+    }
+
+    #[test]
+    fn test_is_subtree_whole_tree() {
+        assert!(is_subtree(Some(build_sample()), Some(build_sample())));
+        assert!(is_subtree_hashed(
+            Some(build_sample()),
+            Some(build_sample())
+        ));
+        assert!(!is_subtree_hashed(
+            Some(build_sample()),
+            Some(build_sample_mirror())
+        ));
+    }
+
+    #[test]
+    fn test_diff_identical() {
+        assert_eq!(diff(Some(build_sample()), Some(build_sample())), None);
+        assert_eq!(diff(None, None), None);
+    }
+
+    #[test]
+    fn test_diff_value() {
+        let b = build_sample();
+        let left = b.borrow().left.clone().unwrap();
+        let left_right = left.borrow().right.clone().unwrap();
+        left_right.borrow_mut().value = 99;
+
+        let result = diff(Some(build_sample()), Some(b));
+        assert_eq!(
+            result,
+            Some(Difference {
+                path: vec![Direction::Left, Direction::Right],
+                a: Some(5),
+                b: Some(99),
+            })
+        );
+    }
+
+    #[test]
+    fn test_diff_missing_node() {
+        let b = build_sample();
+        b.borrow_mut().right = None;
+
+        let result = diff(Some(build_sample()), Some(b));
+        assert_eq!(
+            result,
+            Some(Difference {
+                path: vec![Direction::Right],
+                a: Some(3),
+                b: None,
+            })
+        );
+    }
+
+    #[test]
+    fn test_diff_reports_first_in_preorder() {
+        let b = build_sample_mirror();
+        let result = diff(Some(build_sample()), Some(b)).unwrap();
+        assert_eq!(result.path, vec![Direction::Left]);
+        assert_eq!((result.a, result.b), (Some(2), Some(3)));
+    }
+
+    #[test]
+    fn test_partial_eq_agrees_with_matching() {
+        assert_eq!(*build_sample().borrow(), *build_sample().borrow());
+        assert_ne!(*build_sample().borrow(), *build_sample_mirror().borrow());
+        assert_eq!(Some(build_sample()), Some(build_sample()));
+        assert_ne!(Some(build_sample()), None);
+    }
+
+    // The children sit behind `RefCell`, so a key must not be mutated while
+    // it is stored; the owned deep copies here are never touched again.
+    #[test]
+    #[allow(clippy::mutable_key_type)]
+    fn test_hash_as_map_key() {
+        use std::collections::HashSet;
+
+        // `Rc<RefCell<Node>>` is a shared handle, so the key is the node itself
+        let mut seen = HashSet::new();
+        assert!(seen.insert(build_sample().borrow().clone()));
+        assert!(!seen.insert(build_sample().borrow().clone()));
+        assert!(seen.insert(build_sample_mirror().borrow().clone()));
+        assert_eq!(seen.len(), 2);
+    }
+
+    #[test]
+    fn test_deep_clone_is_independent() {
+        let original = build_sample();
+        let shared = original.clone();
+        let copy = Node::deep_clone(&original);
+        assert_eq!(copy, original);
+
+        let left = copy.borrow().left.clone().unwrap();
+        left.borrow_mut().value = 42;
+
+        assert_ne!(copy, original);
+        assert_eq!(original.borrow().left.as_ref().unwrap().borrow().value, 2);
+        assert!(Rc::ptr_eq(&shared, &original));
+    }
+
+    #[test]
+    fn test_debug_prints_nested_structure() {
+        let root = Node::new(1);
+        root.borrow_mut().right = Some(Node::new(3));
+
+        let output = format!("{:?}", root.borrow());
+        assert_eq!(output, "Node { value: 1, right: Node { value: 3 } }");
     }
 }
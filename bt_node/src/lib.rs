@@ -0,0 +1,248 @@
+//! Shared trait impls for the `Node` types of the binary tree crates.
+//!
+//! Every tree crate defines its own `Node` with a `value` and `left`/`right`
+//! children of type `Option<Rc<RefCell<Node>>>`. [`impl_tree_node!`] gives
+//! such a type structural `PartialEq`/`Eq`/`Hash`, a deep `Clone` with a
+//! `deep_clone` helper, and a `Debug` that omits missing children.
+
+/// Implements `PartialEq`, `Eq`, `Hash`, `Clone` and `Debug` plus
+/// `deep_clone` for a tree node type.
+///
+/// Takes the type name, with its value parameter if it is generic:
+/// `impl_tree_node!(Node)` or `impl_tree_node!(Node<T>)`. The type needs the
+/// fields `value`, `left` and `right`, the latter two holding
+/// `Option<Rc<RefCell<Self>>>`.
+///
+/// Comparison and hashing look at values and shape, not at `Rc` identity.
+/// `Clone` is deep: children are cloned into fresh nodes instead of sharing
+/// the `Rc`, so mutating the copy never touches the original.
+#[macro_export]
+macro_rules! impl_tree_node {
+    ($node:ident) => {
+        $crate::impl_tree_node!(@impl $node []);
+    };
+    ($node:ident<$t:ident>) => {
+        $crate::impl_tree_node!(@impl $node [$t]);
+    };
+    (@impl $node:ident [$($t:ident)?]) => {
+        impl$(<$t>)? $node$(<$t>)? {
+            /// Copies the whole subtree into new nodes, unlike `Rc::clone`
+            /// which shares them.
+            pub fn deep_clone(
+                node: &::std::rc::Rc<::std::cell::RefCell<Self>>,
+            ) -> ::std::rc::Rc<::std::cell::RefCell<Self>>
+            where
+                Self: ::std::clone::Clone,
+            {
+                ::std::rc::Rc::new(::std::cell::RefCell::new(node.borrow().clone()))
+            }
+        }
+
+        impl$(<$t: ::std::cmp::PartialEq>)? ::std::cmp::PartialEq for $node$(<$t>)? {
+            fn eq(&self, other: &Self) -> bool {
+                self.value == other.value && self.left == other.left && self.right == other.right
+            }
+        }
+
+        impl$(<$t: ::std::cmp::Eq>)? ::std::cmp::Eq for $node$(<$t>)? {}
+
+        impl$(<$t: ::std::hash::Hash>)? ::std::hash::Hash for $node$(<$t>)? {
+            fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+                ::std::hash::Hash::hash(&self.value, state);
+                for child in [&self.left, &self.right] {
+                    let child = child.as_ref().map(|node| node.borrow());
+                    ::std::hash::Hash::hash(&child.as_deref(), state);
+                }
+            }
+        }
+
+        impl$(<$t: ::std::clone::Clone>)? ::std::clone::Clone for $node$(<$t>)? {
+            fn clone(&self) -> Self {
+                Self {
+                    value: ::std::clone::Clone::clone(&self.value),
+                    left: self.left.as_ref().map(Self::deep_clone),
+                    right: self.right.as_ref().map(Self::deep_clone),
+                }
+            }
+        }
+
+        impl$(<$t: ::std::fmt::Debug>)? ::std::fmt::Debug for $node$(<$t>)? {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                let mut debug = f.debug_struct(stringify!($node));
+                debug.field("value", &self.value);
+                if let Some(left) = &self.left {
+                    debug.field("left", &*left.borrow());
+                }
+                if let Some(right) = &self.right {
+                    debug.field("right", &*right.borrow());
+                }
+                debug.finish()
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod test {
+    use std::{
+        cell::RefCell,
+        collections::hash_map::DefaultHasher,
+        hash::{Hash, Hasher},
+        rc::Rc,
+    };
+
+    struct Node {
+        value: i32,
+        left: Option<Rc<RefCell<Node>>>,
+        right: Option<Rc<RefCell<Node>>>,
+    }
+
+    impl_tree_node!(Node);
+
+    impl Node {
+        fn new(value: i32) -> Rc<RefCell<Node>> {
+            Rc::new(RefCell::new(Node {
+                value,
+                left: None,
+                right: None,
+            }))
+        }
+    }
+
+    struct GenericNode<T> {
+        value: T,
+        left: Option<Rc<RefCell<GenericNode<T>>>>,
+        right: Option<Rc<RefCell<GenericNode<T>>>>,
+    }
+
+    impl_tree_node!(GenericNode<T>);
+
+    impl<T> GenericNode<T> {
+        fn new(value: T) -> Rc<RefCell<GenericNode<T>>> {
+            Rc::new(RefCell::new(GenericNode {
+                value,
+                left: None,
+                right: None,
+            }))
+        }
+    }
+
+    //      1
+    //     / \
+    //    2   3
+    //   /
+    //  4
+    fn sample() -> Rc<RefCell<Node>> {
+        let root = Node::new(1);
+        let left = Node::new(2);
+        left.borrow_mut().left = Some(Node::new(4));
+        root.borrow_mut().left = Some(left);
+        root.borrow_mut().right = Some(Node::new(3));
+        root
+    }
+
+    fn hash_of<T: Hash>(value: &T) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        value.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    #[test]
+    fn test_equal_trees() {
+        let a = sample();
+        let b = sample();
+
+        assert!(!Rc::ptr_eq(&a, &b));
+        assert_eq!(a, b);
+        assert_eq!(*a.borrow(), *b.borrow());
+    }
+
+    #[test]
+    fn test_unequal_values_and_shapes() {
+        let a = sample();
+
+        let other_value = sample();
+        other_value.borrow_mut().right = Some(Node::new(5));
+        assert_ne!(a, other_value);
+
+        // same values, 4 moved from the left to the right of 2
+        let other_shape = sample();
+        let left = other_shape.borrow().left.clone().unwrap();
+        let moved = left.borrow_mut().left.take();
+        left.borrow_mut().right = moved;
+        assert_ne!(a, other_shape);
+    }
+
+    #[test]
+    fn test_hash_follows_equality() {
+        assert_eq!(hash_of(&*sample().borrow()), hash_of(&*sample().borrow()));
+
+        // a missing child hashes differently from the subtree moved across
+        let shifted = Node::new(1);
+        shifted.borrow_mut().right = Some(Node::new(2));
+        let leaning = Node::new(1);
+        leaning.borrow_mut().left = Some(Node::new(2));
+        assert_ne!(hash_of(&*shifted.borrow()), hash_of(&*leaning.borrow()));
+    }
+
+    #[test]
+    fn test_deep_clone_is_independent() {
+        let root = sample();
+        let copy = Node::deep_clone(&root);
+
+        assert_eq!(copy, root);
+        assert!(!Rc::ptr_eq(&copy, &root));
+
+        let copy_left = copy.borrow().left.clone().unwrap();
+        let root_left = root.borrow().left.clone().unwrap();
+        assert!(!Rc::ptr_eq(&copy_left, &root_left));
+
+        copy_left.borrow_mut().value = 20;
+        copy_left.borrow_mut().left = None;
+        assert_eq!(root_left.borrow().value, 2);
+        assert!(root_left.borrow().left.is_some());
+        assert_ne!(copy, root);
+    }
+
+    #[test]
+    fn test_clone_copies_children() {
+        let root = sample();
+        let copy = root.borrow().clone();
+
+        let right = root.borrow().right.clone().unwrap();
+        assert!(!Rc::ptr_eq(copy.right.as_ref().unwrap(), &right));
+        assert_eq!(copy, *root.borrow());
+    }
+
+    #[test]
+    fn test_debug_skips_missing_children() {
+        assert_eq!(
+            format!("{:?}", sample().borrow()),
+            "Node { value: 1, left: Node { value: 2, left: Node { value: 4 } }, \
+             right: Node { value: 3 } }"
+        );
+    }
+
+    #[test]
+    fn test_generic_node() {
+        let root = GenericNode::new(String::from("b"));
+        root.borrow_mut().left = Some(GenericNode::new(String::from("a")));
+
+        let copy = GenericNode::deep_clone(&root);
+        assert_eq!(copy, root);
+        assert_eq!(hash_of(&*copy.borrow()), hash_of(&*root.borrow()));
+
+        copy.borrow()
+            .left
+            .as_ref()
+            .unwrap()
+            .borrow_mut()
+            .value
+            .push('z');
+        assert_eq!(root.borrow().left.as_ref().unwrap().borrow().value, "a");
+        assert_eq!(
+            format!("{:?}", root.borrow()),
+            r#"GenericNode { value: "b", left: GenericNode { value: "a" } }"#
+        );
+    }
+}
@@ -0,0 +1,331 @@
+use std::{cell::RefCell, collections::HashMap, rc::Rc};
+
+pub struct Node {
+    pub value: i32,
+    pub left: Option<Rc<RefCell<Node>>>,
+    pub right: Option<Rc<RefCell<Node>>>,
+}
+
+bt_node::impl_tree_node!(Node);
+
+impl Node {
+    pub fn new(value: i32) -> Rc<RefCell<Node>> {
+        let node = Self {
+            value,
+            left: None,
+            right: None,
+        };
+
+        Rc::new(RefCell::new(node))
+    }
+}
+
+/// Every root-to-leaf path, left to right.
+pub fn all_paths(root: Option<Rc<RefCell<Node>>>) -> Vec<Vec<i32>> {
+    let mut result = Vec::new();
+    let mut path = Vec::new();
+    collect_paths(root, &mut path, &mut result);
+    result
+}
+
+fn collect_paths(node: Option<Rc<RefCell<Node>>>, path: &mut Vec<i32>, result: &mut Vec<Vec<i32>>) {
+    let node = match node {
+        Some(node) => node,
+        None => return,
+    };
+
+    let borrowed = node.borrow();
+    path.push(borrowed.value);
+    if borrowed.left.is_none() && borrowed.right.is_none() {
+        result.push(path.clone());
+    } else {
+        collect_paths(borrowed.left.clone(), path, result);
+        collect_paths(borrowed.right.clone(), path, result);
+    }
+    path.pop();
+}
+
+/// Returns true if some root-to-leaf path adds up to `target`.
+pub fn has_path_sum(root: Option<Rc<RefCell<Node>>>, target: i64) -> bool {
+    let node = match root {
+        Some(node) => node,
+        None => return false,
+    };
+
+    let borrowed = node.borrow();
+    // no path sum of `i32` values comes near the `i64` limits, so a target
+    // that far out cannot be reached
+    let remaining = match target.checked_sub(borrowed.value as i64) {
+        Some(remaining) => remaining,
+        None => return false,
+    };
+    if borrowed.left.is_none() && borrowed.right.is_none() {
+        return remaining == 0;
+    }
+
+    has_path_sum(borrowed.left.clone(), remaining)
+        || has_path_sum(borrowed.right.clone(), remaining)
+}
+
+/// Counts the downward paths (parent to child, starting and ending anywhere)
+/// that add up to `target`.
+///
+/// Keeps a running prefix sum along the current root path: a path ending at
+/// the current node sums to `target` exactly when an earlier prefix equals
+/// `current - target`. Runs in `O(n)`.
+pub fn count_path_sums(root: Option<Rc<RefCell<Node>>>, target: i64) -> usize {
+    let mut prefixes = HashMap::new();
+    prefixes.insert(0, 1);
+    count_from(root, 0, target, &mut prefixes)
+}
+
+fn count_from(
+    node: Option<Rc<RefCell<Node>>>,
+    sum: i64,
+    target: i64,
+    prefixes: &mut HashMap<i64, usize>,
+) -> usize {
+    let node = match node {
+        Some(node) => node,
+        None => return 0,
+    };
+
+    let borrowed = node.borrow();
+    let sum = sum + borrowed.value as i64;
+    // an out of range `sum - target` cannot be a recorded prefix
+    let mut count = sum
+        .checked_sub(target)
+        .and_then(|prefix| prefixes.get(&prefix))
+        .copied()
+        .unwrap_or(0);
+
+    *prefixes.entry(sum).or_insert(0) += 1;
+    count += count_from(borrowed.left.clone(), sum, target, prefixes);
+    count += count_from(borrowed.right.clone(), sum, target, prefixes);
+    *prefixes.get_mut(&sum).unwrap() -= 1;
+
+    count
+}
+
+/// Largest sum of any non-empty path between two nodes, or `None` for an
+/// empty tree. The path may start and end at any node but cannot branch.
+pub fn max_path_sum(root: Option<Rc<RefCell<Node>>>) -> Option<i64> {
+    let mut best = None;
+    max_gain(root, &mut best);
+    best
+}
+
+// Returns the best sum of a path that starts at `node` and goes down, while
+// recording in `best` the best path that bends at `node`.
+fn max_gain(node: Option<Rc<RefCell<Node>>>, best: &mut Option<i64>) -> i64 {
+    let node = match node {
+        Some(node) => node,
+        None => return 0,
+    };
+
+    let borrowed = node.borrow();
+    let left = max_gain(borrowed.left.clone(), best).max(0);
+    let right = max_gain(borrowed.right.clone(), best).max(0);
+    let value = borrowed.value as i64;
+
+    let through = value + left + right;
+    *best = Some(best.map_or(through, |best| best.max(through)));
+
+    value + left.max(right)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    //        5
+    //       / \
+    //      4   8
+    //     /   / \
+    //    11  13  4
+    //   /  \    / \
+    //  7    2  5   1
+    fn build_sample() -> Rc<RefCell<Node>> {
+        let root = Node::new(5);
+        root.borrow_mut().left = Some(Node::new(4));
+        root.borrow_mut().right = Some(Node::new(8));
+
+        let left = root.borrow().left.clone().unwrap();
+        left.borrow_mut().left = Some(Node::new(11));
+
+        let left_left = left.borrow().left.clone().unwrap();
+        left_left.borrow_mut().left = Some(Node::new(7));
+        left_left.borrow_mut().right = Some(Node::new(2));
+
+        let right = root.borrow().right.clone().unwrap();
+        right.borrow_mut().left = Some(Node::new(13));
+        right.borrow_mut().right = Some(Node::new(4));
+
+        let right_right = right.borrow().right.clone().unwrap();
+        right_right.borrow_mut().left = Some(Node::new(5));
+        right_right.borrow_mut().right = Some(Node::new(1));
+        root
+    }
+
+    #[test]
+    fn test_empty_tree() {
+        assert!(all_paths(None).is_empty());
+        assert!(!has_path_sum(None, 0));
+        assert_eq!(count_path_sums(None, 0), 0);
+        assert_eq!(max_path_sum(None), None);
+    }
+
+    #[test]
+    fn test_single_node() {
+        let root = Node::new(-3);
+        assert_eq!(all_paths(Some(root.clone())), vec![vec![-3]]);
+        assert!(has_path_sum(Some(root.clone()), -3));
+        assert_eq!(count_path_sums(Some(root.clone()), -3), 1);
+        assert_eq!(max_path_sum(Some(root)), Some(-3));
+    }
+
+    #[test]
+    fn test_all_paths() {
+        let result = all_paths(Some(build_sample()));
+        assert_eq!(
+            result,
+            vec![
+                vec![5, 4, 11, 7],
+                vec![5, 4, 11, 2],
+                vec![5, 8, 13],
+                vec![5, 8, 4, 5],
+                vec![5, 8, 4, 1],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_has_path_sum() {
+        let root = build_sample();
+        assert!(has_path_sum(Some(root.clone()), 22));
+        assert!(has_path_sum(Some(root.clone()), 26));
+        assert!(!has_path_sum(Some(root.clone()), 5));
+        // 5 -> 4 -> 11 is 20 but 11 is not a leaf
+        assert!(!has_path_sum(Some(root), 20));
+    }
+
+    #[test]
+    fn test_has_path_sum_one_sided_node() {
+        // 1 has only a left child, so the path must go through 2
+        let root = Node::new(1);
+        root.borrow_mut().left = Some(Node::new(2));
+
+        assert!(!has_path_sum(Some(root.clone()), 1));
+        assert!(has_path_sum(Some(root), 3));
+    }
+
+    #[test]
+    fn test_count_path_sums() {
+        //       10
+        //      /  \
+        //     5   -3
+        //    / \    \
+        //   3   2    11
+        //  / \   \
+        // 3  -2   1
+        let root = Node::new(10);
+        root.borrow_mut().left = Some(Node::new(5));
+        root.borrow_mut().right = Some(Node::new(-3));
+
+        let left = root.borrow().left.clone().unwrap();
+        left.borrow_mut().left = Some(Node::new(3));
+        left.borrow_mut().right = Some(Node::new(2));
+
+        let left_left = left.borrow().left.clone().unwrap();
+        left_left.borrow_mut().left = Some(Node::new(3));
+        left_left.borrow_mut().right = Some(Node::new(-2));
+
+        let left_right = left.borrow().right.clone().unwrap();
+        left_right.borrow_mut().right = Some(Node::new(1));
+
+        let right = root.borrow().right.clone().unwrap();
+        right.borrow_mut().right = Some(Node::new(11));
+
+        // 5 -> 3, 5 -> 2 -> 1, -3 -> 11
+        assert_eq!(count_path_sums(Some(root), 8), 3);
+    }
+
+    #[test]
+    fn test_count_path_sums_with_zeroes() {
+        // every downward path of 0 -> 0 -> 0 sums to zero
+        let root = Node::new(0);
+        root.borrow_mut().left = Some(Node::new(0));
+        let left = root.borrow().left.clone().unwrap();
+        left.borrow_mut().left = Some(Node::new(0));
+
+        assert_eq!(count_path_sums(Some(root), 0), 6);
+    }
+
+    #[test]
+    fn test_count_path_sums_sample() {
+        // 5 -> 4 -> 11 -> 2, 5 -> 8 -> 4 -> 5, 4 -> 11 -> 7
+        assert_eq!(count_path_sums(Some(build_sample()), 22), 3);
+    }
+
+    #[test]
+    fn test_extreme_targets() {
+        let root = Node::new(-3);
+        root.borrow_mut().left = Some(Node::new(i32::MAX));
+        root.borrow_mut().right = Some(Node::new(i32::MIN));
+
+        for target in [i64::MIN, i64::MAX] {
+            assert!(!has_path_sum(Some(root.clone()), target));
+            assert!(!has_path_sum(Some(build_sample()), target));
+            assert_eq!(count_path_sums(Some(root.clone()), target), 0);
+            assert_eq!(count_path_sums(Some(build_sample()), target), 0);
+        }
+
+        assert!(has_path_sum(Some(root.clone()), i32::MIN as i64 - 3));
+        assert_eq!(count_path_sums(Some(root), i32::MIN as i64), 1);
+    }
+
+    #[test]
+    fn test_max_path_sum() {
+        //    -10
+        //    /  \
+        //   9   20
+        //      /  \
+        //     15   7
+        let root = Node::new(-10);
+        root.borrow_mut().left = Some(Node::new(9));
+        root.borrow_mut().right = Some(Node::new(20));
+
+        let right = root.borrow().right.clone().unwrap();
+        right.borrow_mut().left = Some(Node::new(15));
+        right.borrow_mut().right = Some(Node::new(7));
+
+        assert_eq!(max_path_sum(Some(root)), Some(42));
+    }
+
+    #[test]
+    fn test_max_path_sum_through_root() {
+        let root = Node::new(1);
+        root.borrow_mut().left = Some(Node::new(2));
+        root.borrow_mut().right = Some(Node::new(3));
+
+        assert_eq!(max_path_sum(Some(root)), Some(6));
+    }
+
+    #[test]
+    fn test_max_path_sum_all_negative() {
+        let root = Node::new(-3);
+        root.borrow_mut().left = Some(Node::new(-1));
+        root.borrow_mut().right = Some(Node::new(-2));
+
+        assert_eq!(max_path_sum(Some(root)), Some(-1));
+    }
+
+    #[test]
+    fn test_max_path_sum_does_not_overflow() {
+        let root = Node::new(i32::MAX);
+        root.borrow_mut().left = Some(Node::new(i32::MAX));
+        root.borrow_mut().right = Some(Node::new(i32::MAX));
+
+        assert_eq!(max_path_sum(Some(root)), Some(3 * i32::MAX as i64));
+    }
+}
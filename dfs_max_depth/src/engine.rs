@@ -0,0 +1,478 @@
+use std::{cell::RefCell, rc::Rc};
+
+use crate::Node;
+
+/// What the traversal should do after a callback.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Control {
+    Continue,
+    /// From `pre_visit`: skip the node's children (`post_visit` still runs).
+    /// From `edge`: skip that child.
+    Prune,
+    /// Abort the whole traversal; no further callbacks are made.
+    Stop,
+}
+
+/// Where the traversal is when a callback runs.
+#[derive(Debug)]
+pub struct Context<'a, T> {
+    /// Number of edges from the start node; the start node is at depth 0.
+    pub depth: usize,
+    /// Start node up to and including the current node.
+    pub path: &'a [T],
+}
+
+/// Callbacks for [`walk`]. Every method defaults to [`Control::Continue`].
+pub trait Visitor {
+    fn pre_visit(&mut self, _node: &Node, _ctx: &Context<i32>) -> Control {
+        Control::Continue
+    }
+
+    fn post_visit(&mut self, _node: &Node, _ctx: &Context<i32>) -> Control {
+        Control::Continue
+    }
+
+    /// Called before descending from `parent` to `child`. `ctx` describes the
+    /// parent.
+    fn edge(&mut self, _parent: &Node, _child: &Node, _ctx: &Context<i32>) -> Control {
+        Control::Continue
+    }
+}
+
+/// Depth-first traversal of a tree, left child before right child.
+///
+/// Returns `false` if a callback returned [`Control::Stop`].
+pub fn walk<V: Visitor>(root: Option<Rc<RefCell<Node>>>, visitor: &mut V) -> bool {
+    let mut path = Vec::new();
+    match root {
+        Some(root) => walk_node(&root, &mut path, visitor) != Control::Stop,
+        None => true,
+    }
+}
+
+fn walk_node<V: Visitor>(
+    node: &Rc<RefCell<Node>>,
+    path: &mut Vec<i32>,
+    visitor: &mut V,
+) -> Control {
+    let borrowed = node.borrow();
+    path.push(borrowed.value);
+    let control = visit_node(&borrowed, path, visitor);
+    path.pop();
+    control
+}
+
+fn visit_node<V: Visitor>(node: &Node, path: &mut Vec<i32>, visitor: &mut V) -> Control {
+    let depth = path.len() - 1;
+
+    let control = visitor.pre_visit(node, &Context { depth, path });
+    if control == Control::Stop {
+        return Control::Stop;
+    }
+
+    if control == Control::Continue {
+        for child in [&node.left, &node.right].into_iter().flatten() {
+            let ctx = Context { depth, path };
+            match visitor.edge(node, &child.borrow(), &ctx) {
+                Control::Stop => return Control::Stop,
+                Control::Prune => continue,
+                Control::Continue => {}
+            }
+
+            if walk_node(child, path, visitor) == Control::Stop {
+                return Control::Stop;
+            }
+        }
+    }
+
+    match visitor.post_visit(node, &Context { depth, path }) {
+        Control::Stop => Control::Stop,
+        _ => Control::Continue,
+    }
+}
+
+/// Callbacks for [`walk_graph`]. Every method defaults to
+/// [`Control::Continue`].
+pub trait GraphVisitor {
+    fn pre_visit(&mut self, _node: usize, _ctx: &Context<usize>) -> Control {
+        Control::Continue
+    }
+
+    fn post_visit(&mut self, _node: usize, _ctx: &Context<usize>) -> Control {
+        Control::Continue
+    }
+
+    /// Called for every outgoing edge of `from`. `visited` is true when `to`
+    /// was already reached, in which case it is not entered again. `ctx`
+    /// describes `from`.
+    fn edge(&mut self, _from: usize, _to: usize, _visited: bool, _ctx: &Context<usize>) -> Control {
+        Control::Continue
+    }
+}
+
+/// Depth-first traversal of an adjacency-list graph from `start`, visiting
+/// every reachable node once. Neighbors are explored in list order.
+///
+/// Keeps its own stack instead of recursing, so long paths cannot overflow
+/// the call stack.
+///
+/// Returns `false` if a callback returned [`Control::Stop`].
+///
+/// # Panics
+/// Panics if `start` or a reached edge points past the end of `graph`.
+pub fn walk_graph<V: GraphVisitor>(graph: &[Vec<usize>], start: usize, visitor: &mut V) -> bool {
+    assert!(start < graph.len(), "node out of range");
+    let mut walk = GraphWalk {
+        graph,
+        visited: vec![false; graph.len()],
+        path: Vec::new(),
+        next_edge: Vec::new(),
+    };
+    if !walk.enter(start, visitor) {
+        return false;
+    }
+
+    while let (Some(&node), Some(index)) = (walk.path.last(), walk.next_edge.last_mut()) {
+        let depth = walk.path.len() - 1;
+        let Some(&next) = graph[node].get(*index) else {
+            let ctx = Context {
+                depth,
+                path: &walk.path,
+            };
+            if visitor.post_visit(node, &ctx) == Control::Stop {
+                return false;
+            }
+            walk.path.pop();
+            walk.next_edge.pop();
+            continue;
+        };
+        *index += 1;
+
+        assert!(next < graph.len(), "node out of range");
+        let seen = walk.visited[next];
+        let ctx = Context {
+            depth,
+            path: &walk.path,
+        };
+        match visitor.edge(node, next, seen, &ctx) {
+            Control::Stop => return false,
+            Control::Prune => continue,
+            Control::Continue => {}
+        }
+
+        if !seen && !walk.enter(next, visitor) {
+            return false;
+        }
+    }
+
+    true
+}
+
+struct GraphWalk<'a> {
+    graph: &'a [Vec<usize>],
+    visited: Vec<bool>,
+    // the nodes from the start to the current one, and for each the index
+    // of its next neighbor to explore
+    path: Vec<usize>,
+    next_edge: Vec<usize>,
+}
+
+impl GraphWalk<'_> {
+    // Marks `node` visited, pushes it on the path and runs `pre_visit`.
+    // Returns `false` on `Stop`. A pruned node gets no edges to explore.
+    fn enter<V: GraphVisitor>(&mut self, node: usize, visitor: &mut V) -> bool {
+        self.visited[node] = true;
+        self.path.push(node);
+        let ctx = Context {
+            depth: self.path.len() - 1,
+            path: &self.path,
+        };
+        let first_edge = match visitor.pre_visit(node, &ctx) {
+            Control::Stop => return false,
+            Control::Prune => self.graph[node].len(),
+            Control::Continue => 0,
+        };
+        self.next_edge.push(first_edge);
+        true
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // Records every callback in order.
+    #[derive(Default)]
+    struct Recorder {
+        events: Vec<String>,
+        prune_at: Option<i32>,
+        stop_at: Option<i32>,
+    }
+
+    impl Visitor for Recorder {
+        fn pre_visit(&mut self, node: &Node, ctx: &Context<i32>) -> Control {
+            self.events
+                .push(format!("pre {} @{}", node.value, ctx.depth));
+            if self.stop_at == Some(node.value) {
+                return Control::Stop;
+            }
+            if self.prune_at == Some(node.value) {
+                return Control::Prune;
+            }
+            Control::Continue
+        }
+
+        fn post_visit(&mut self, node: &Node, _ctx: &Context<i32>) -> Control {
+            self.events.push(format!("post {}", node.value));
+            Control::Continue
+        }
+
+        fn edge(&mut self, parent: &Node, child: &Node, _ctx: &Context<i32>) -> Control {
+            self.events
+                .push(format!("edge {}->{}", parent.value, child.value));
+            Control::Continue
+        }
+    }
+
+    //      1
+    //     / \
+    //    2   3
+    //   /
+    //  4
+    fn build_sample() -> Rc<RefCell<Node>> {
+        let root = Node::new(1);
+        let left = Node::new(2);
+        left.borrow_mut().set_left(Node::new(4));
+        root.borrow_mut().set_left(left);
+        root.borrow_mut().set_right(Node::new(3));
+        root
+    }
+
+    #[test]
+    fn test_walk_empty_tree() {
+        let mut recorder = Recorder::default();
+        assert!(walk(None, &mut recorder));
+        assert!(recorder.events.is_empty());
+    }
+
+    #[test]
+    fn test_walk_callback_order() {
+        let mut recorder = Recorder::default();
+        assert!(walk(Some(build_sample()), &mut recorder));
+        assert_eq!(
+            recorder.events,
+            vec![
+                "pre 1 @0",
+                "edge 1->2",
+                "pre 2 @1",
+                "edge 2->4",
+                "pre 4 @2",
+                "post 4",
+                "post 2",
+                "edge 1->3",
+                "pre 3 @1",
+                "post 3",
+                "post 1",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_walk_prune_skips_children() {
+        let mut recorder = Recorder {
+            prune_at: Some(2),
+            ..Default::default()
+        };
+        assert!(walk(Some(build_sample()), &mut recorder));
+        assert!(!recorder.events.iter().any(|event| event.contains('4')));
+        assert!(recorder.events.contains(&"post 2".to_string()));
+        assert!(recorder.events.contains(&"pre 3 @1".to_string()));
+    }
+
+    #[test]
+    fn test_walk_stop() {
+        let mut recorder = Recorder {
+            stop_at: Some(4),
+            ..Default::default()
+        };
+        assert!(!walk(Some(build_sample()), &mut recorder));
+        assert_eq!(recorder.events.last().unwrap(), "pre 4 @2");
+    }
+
+    #[test]
+    fn test_walk_edge_prune() {
+        struct LeftOnly(Vec<i32>);
+
+        impl Visitor for LeftOnly {
+            fn pre_visit(&mut self, node: &Node, _ctx: &Context<i32>) -> Control {
+                self.0.push(node.value);
+                Control::Continue
+            }
+
+            fn edge(&mut self, parent: &Node, child: &Node, _ctx: &Context<i32>) -> Control {
+                let is_left = parent
+                    .left
+                    .as_ref()
+                    .is_some_and(|left| std::ptr::eq(&*left.borrow(), child));
+                if is_left {
+                    Control::Continue
+                } else {
+                    Control::Prune
+                }
+            }
+        }
+
+        let mut visitor = LeftOnly(Vec::new());
+        walk(Some(build_sample()), &mut visitor);
+        assert_eq!(visitor.0, vec![1, 2, 4]);
+    }
+
+    #[test]
+    fn test_walk_path_context() {
+        struct Paths(Vec<Vec<i32>>);
+
+        impl Visitor for Paths {
+            fn pre_visit(&mut self, _node: &Node, ctx: &Context<i32>) -> Control {
+                assert_eq!(ctx.path.len(), ctx.depth + 1);
+                self.0.push(ctx.path.to_vec());
+                Control::Continue
+            }
+        }
+
+        let mut visitor = Paths(Vec::new());
+        walk(Some(build_sample()), &mut visitor);
+        assert_eq!(
+            visitor.0,
+            vec![vec![1], vec![1, 2], vec![1, 2, 4], vec![1, 3]]
+        );
+    }
+
+    #[derive(Default)]
+    struct GraphRecorder {
+        order: Vec<usize>,
+        finished: Vec<usize>,
+        back_edges: Vec<(usize, usize)>,
+    }
+
+    impl GraphVisitor for GraphRecorder {
+        fn pre_visit(&mut self, node: usize, _ctx: &Context<usize>) -> Control {
+            self.order.push(node);
+            Control::Continue
+        }
+
+        fn post_visit(&mut self, node: usize, _ctx: &Context<usize>) -> Control {
+            self.finished.push(node);
+            Control::Continue
+        }
+
+        fn edge(&mut self, from: usize, to: usize, visited: bool, ctx: &Context<usize>) -> Control {
+            // an edge back into the current path closes a cycle
+            if visited && ctx.path.contains(&to) {
+                self.back_edges.push((from, to));
+            }
+            Control::Continue
+        }
+    }
+
+    #[test]
+    fn test_walk_graph_order() {
+        // 0 -> 1 -> 3, 0 -> 2 -> 3
+        let graph = vec![vec![1, 2], vec![3], vec![3], vec![]];
+        let mut recorder = GraphRecorder::default();
+        assert!(walk_graph(&graph, 0, &mut recorder));
+
+        assert_eq!(recorder.order, vec![0, 1, 3, 2]);
+        assert_eq!(recorder.finished, vec![3, 1, 2, 0]);
+        assert!(recorder.back_edges.is_empty());
+    }
+
+    #[test]
+    fn test_walk_graph_detects_cycle() {
+        // 0 -> 1 -> 2 -> 0
+        let graph = vec![vec![1], vec![2], vec![0]];
+        let mut recorder = GraphRecorder::default();
+        walk_graph(&graph, 0, &mut recorder);
+
+        assert_eq!(recorder.order, vec![0, 1, 2]);
+        assert_eq!(recorder.back_edges, vec![(2, 0)]);
+    }
+
+    #[test]
+    fn test_walk_graph_stop() {
+        struct FindNode(usize);
+
+        impl GraphVisitor for FindNode {
+            fn pre_visit(&mut self, node: usize, _ctx: &Context<usize>) -> Control {
+                if node == self.0 {
+                    Control::Stop
+                } else {
+                    Control::Continue
+                }
+            }
+        }
+
+        let graph = vec![vec![1], vec![0, 2], vec![1]];
+        assert!(!walk_graph(&graph, 0, &mut FindNode(2)));
+        assert!(walk_graph(&graph, 0, &mut FindNode(7)));
+    }
+
+    #[test]
+    fn test_walk_graph_prune() {
+        struct PruneAt(usize, Vec<usize>);
+
+        impl GraphVisitor for PruneAt {
+            fn pre_visit(&mut self, node: usize, _ctx: &Context<usize>) -> Control {
+                self.1.push(node);
+                if node == self.0 {
+                    Control::Prune
+                } else {
+                    Control::Continue
+                }
+            }
+        }
+
+        // 0 -> 1 -> 3, 0 -> 2
+        let graph = vec![vec![1, 2], vec![3], vec![], vec![]];
+        let mut visitor = PruneAt(1, Vec::new());
+        assert!(walk_graph(&graph, 0, &mut visitor));
+        assert_eq!(visitor.1, vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn test_walk_graph_long_path() {
+        struct Deepest(usize);
+
+        impl GraphVisitor for Deepest {
+            fn pre_visit(&mut self, _node: usize, ctx: &Context<usize>) -> Control {
+                self.0 = self.0.max(ctx.depth);
+                Control::Continue
+            }
+        }
+
+        let nodes = 1_000_000;
+        let graph: Vec<Vec<usize>> = (0..nodes)
+            .map(|node| {
+                if node + 1 < nodes {
+                    vec![node + 1]
+                } else {
+                    vec![]
+                }
+            })
+            .collect();
+        let mut visitor = Deepest(0);
+        assert!(walk_graph(&graph, 0, &mut visitor));
+        assert_eq!(visitor.0, nodes - 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "node out of range")]
+    fn test_walk_graph_start_out_of_range() {
+        walk_graph(&[vec![]], 1, &mut GraphRecorder::default());
+    }
+
+    #[test]
+    #[should_panic(expected = "node out of range")]
+    fn test_walk_graph_edge_out_of_range() {
+        walk_graph(&[vec![1]], 0, &mut GraphRecorder::default());
+    }
+}
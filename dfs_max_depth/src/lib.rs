@@ -1,13 +1,19 @@
-#![allow(dead_code)]
-
 use std::{cell::RefCell, rc::Rc};
 
+use engine::walk;
+use visitors::MaxDepth;
+
+pub mod engine;
+pub mod visitors;
+
 pub struct Node {
     pub value: i32,
     left: Option<Rc<RefCell<Node>>>,
     right: Option<Rc<RefCell<Node>>>,
 }
 
+bt_node::impl_tree_node!(Node);
+
 impl Node {
     pub fn new(value: i32) -> Rc<RefCell<Node>> {
         Rc::new(RefCell::new(Node {
@@ -24,26 +30,18 @@ impl Node {
     pub fn set_right(&mut self, node: Rc<RefCell<Node>>) {
         self.right = Some(node);
     }
-}
 
-fn max_depth(root: Rc<RefCell<Node>>, depth: i32) -> i32 {
-    let left_depth = match root.borrow().left.clone() {
-        Some(node) => max_depth(node, depth),
-        None => 0,
-    };
-
-    let right_depth = match root.borrow().right.clone() {
-        Some(node) => max_depth(node, depth),
-        None => 0,
-    };
-
-    if right_depth > left_depth {
-        right_depth + 1
-    } else {
-        left_depth + 1
+    pub fn is_leaf(&self) -> bool {
+        self.left.is_none() && self.right.is_none()
     }
 }
 
+pub fn max_depth(root: Rc<RefCell<Node>>) -> i32 {
+    let mut visitor = MaxDepth::default();
+    walk(Some(root), &mut visitor);
+    visitor.depth as i32
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -51,7 +49,7 @@ mod test {
     #[test]
     fn test_max_depth_single_node() {
         let root = Node::new(1);
-        let max = max_depth(root, 0);
+        let max = max_depth(root);
         assert_eq!(max, 1);
     }
 
@@ -61,7 +59,7 @@ mod test {
         root.borrow_mut().set_left(Node::new(2));
         root.borrow_mut().set_right(Node::new(3));
 
-        let max = max_depth(root, 0);
+        let max = max_depth(root);
         assert_eq!(max, 2);
     }
 
@@ -72,7 +70,7 @@ mod test {
         left.borrow_mut().set_left(Node::new(3));
         root.borrow_mut().set_left(left);
 
-        let max = max_depth(root, 0);
+        let max = max_depth(root);
         assert_eq!(max, 3);
     }
 
@@ -83,7 +81,7 @@ mod test {
         right.borrow_mut().set_right(Node::new(3));
         root.borrow_mut().set_right(right);
 
-        let max = max_depth(root, 0);
+        let max = max_depth(root);
         assert_eq!(max, 3);
     }
 
@@ -105,7 +103,7 @@ mod test {
         right.borrow_mut().set_left(right_left);
         root.borrow_mut().set_right(right);
 
-        let max = max_depth(root, 0);
+        let max = max_depth(root);
         assert_eq!(max, 5);
     }
 
@@ -126,7 +124,7 @@ mod test {
         root.borrow_mut().set_left(left);
         root.borrow_mut().set_right(right);
 
-        let max = max_depth(root, 0);
+        let max = max_depth(root);
         assert_eq!(max, 3);
     }
 
@@ -135,7 +133,7 @@ mod test {
         let root = Node::new(1);
         root.borrow_mut().set_left(Node::new(2));
 
-        let max = max_depth(root, 0);
+        let max = max_depth(root);
         assert_eq!(max, 2);
     }
 
@@ -144,7 +142,7 @@ mod test {
         let root = Node::new(1);
         root.borrow_mut().set_right(Node::new(2));
 
-        let max = max_depth(root, 0);
+        let max = max_depth(root);
         assert_eq!(max, 2);
     }
 
@@ -163,7 +161,7 @@ mod test {
         n2.borrow_mut().set_right(n3);
         root.borrow_mut().set_left(n2);
 
-        let max = max_depth(root, 0);
+        let max = max_depth(root);
         assert_eq!(max, 6);
     }
 }
@@ -0,0 +1,199 @@
+use crate::{
+    Node,
+    engine::{Context, Control, Visitor},
+};
+
+/// Number of nodes on the longest root-to-leaf path.
+#[derive(Debug, Default)]
+pub struct MaxDepth {
+    pub depth: usize,
+}
+
+impl Visitor for MaxDepth {
+    fn pre_visit(&mut self, _node: &Node, ctx: &Context<i32>) -> Control {
+        self.depth = self.depth.max(ctx.depth + 1);
+        Control::Continue
+    }
+}
+
+/// Every root-to-leaf path, left to right.
+#[derive(Debug, Default)]
+pub struct RootToLeafPaths {
+    pub paths: Vec<Vec<i32>>,
+}
+
+impl Visitor for RootToLeafPaths {
+    fn pre_visit(&mut self, node: &Node, ctx: &Context<i32>) -> Control {
+        if node.is_leaf() {
+            self.paths.push(ctx.path.to_vec());
+        }
+        Control::Continue
+    }
+}
+
+/// Finds the first root-to-leaf path whose values add up to `target`, and
+/// stops the traversal as soon as it is found.
+#[derive(Debug)]
+pub struct PathSum {
+    target: i64,
+    pub found: Option<Vec<i32>>,
+}
+
+impl PathSum {
+    pub fn new(target: i64) -> Self {
+        Self {
+            target,
+            found: None,
+        }
+    }
+}
+
+impl Visitor for PathSum {
+    fn pre_visit(&mut self, node: &Node, ctx: &Context<i32>) -> Control {
+        if !node.is_leaf() {
+            return Control::Continue;
+        }
+
+        let sum: i64 = ctx.path.iter().map(|&value| value as i64).sum();
+        if sum == self.target {
+            self.found = Some(ctx.path.to_vec());
+            return Control::Stop;
+        }
+        Control::Continue
+    }
+}
+
+/// Searches for `target` without going deeper than `limit` edges below the
+/// root. `found` holds the path to the first match.
+#[derive(Debug)]
+pub struct DepthLimitedSearch {
+    target: i32,
+    limit: usize,
+    pub found: Option<Vec<i32>>,
+}
+
+impl DepthLimitedSearch {
+    pub fn new(target: i32, limit: usize) -> Self {
+        Self {
+            target,
+            limit,
+            found: None,
+        }
+    }
+}
+
+impl Visitor for DepthLimitedSearch {
+    fn pre_visit(&mut self, node: &Node, ctx: &Context<i32>) -> Control {
+        if node.value == self.target {
+            self.found = Some(ctx.path.to_vec());
+            return Control::Stop;
+        }
+
+        if ctx.depth >= self.limit {
+            Control::Prune
+        } else {
+            Control::Continue
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{cell::RefCell, rc::Rc};
+
+    use super::*;
+    use crate::engine::walk;
+
+    //        5
+    //       / \
+    //      4   8
+    //     /   / \
+    //    11  13  4
+    //   /  \      \
+    //  7    2      1
+    fn build_sample() -> Rc<RefCell<Node>> {
+        let root = Node::new(5);
+
+        let n4 = Node::new(4);
+        let n11 = Node::new(11);
+        n11.borrow_mut().set_left(Node::new(7));
+        n11.borrow_mut().set_right(Node::new(2));
+        n4.borrow_mut().set_left(n11);
+
+        let n8 = Node::new(8);
+        let n4_right = Node::new(4);
+        n4_right.borrow_mut().set_right(Node::new(1));
+        n8.borrow_mut().set_left(Node::new(13));
+        n8.borrow_mut().set_right(n4_right);
+
+        root.borrow_mut().set_left(n4);
+        root.borrow_mut().set_right(n8);
+        root
+    }
+
+    #[test]
+    fn test_max_depth_visitor() {
+        let mut visitor = MaxDepth::default();
+        walk(Some(build_sample()), &mut visitor);
+        assert_eq!(visitor.depth, 4);
+
+        let mut visitor = MaxDepth::default();
+        walk(None, &mut visitor);
+        assert_eq!(visitor.depth, 0);
+    }
+
+    #[test]
+    fn test_root_to_leaf_paths() {
+        let mut visitor = RootToLeafPaths::default();
+        walk(Some(build_sample()), &mut visitor);
+        assert_eq!(
+            visitor.paths,
+            vec![
+                vec![5, 4, 11, 7],
+                vec![5, 4, 11, 2],
+                vec![5, 8, 13],
+                vec![5, 8, 4, 1],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_path_sum_found() {
+        let mut visitor = PathSum::new(22);
+        let completed = walk(Some(build_sample()), &mut visitor);
+
+        assert!(!completed);
+        assert_eq!(visitor.found, Some(vec![5, 4, 11, 2]));
+    }
+
+    #[test]
+    fn test_path_sum_ignores_partial_paths() {
+        // 5 -> 4 sums to 9 but 4 is not a leaf
+        let mut visitor = PathSum::new(9);
+        assert!(walk(Some(build_sample()), &mut visitor));
+        assert_eq!(visitor.found, None);
+    }
+
+    #[test]
+    fn test_depth_limited_search() {
+        let mut visitor = DepthLimitedSearch::new(13, 2);
+        walk(Some(build_sample()), &mut visitor);
+        assert_eq!(visitor.found, Some(vec![5, 8, 13]));
+
+        // 1 is three edges deep
+        let mut visitor = DepthLimitedSearch::new(1, 2);
+        walk(Some(build_sample()), &mut visitor);
+        assert_eq!(visitor.found, None);
+
+        let mut visitor = DepthLimitedSearch::new(1, 3);
+        walk(Some(build_sample()), &mut visitor);
+        assert_eq!(visitor.found, Some(vec![5, 8, 4, 1]));
+    }
+
+    #[test]
+    fn test_depth_limited_search_root() {
+        let mut visitor = DepthLimitedSearch::new(5, 0);
+        walk(Some(build_sample()), &mut visitor);
+        assert_eq!(visitor.found, Some(vec![5]));
+    }
+}
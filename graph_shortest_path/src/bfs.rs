@@ -0,0 +1,221 @@
+use std::collections::VecDeque;
+
+/// Everything a breadth-first search from one node finds, created by
+/// [`bfs_tree`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BfsTree {
+    pub source: usize,
+    /// Hops from the source, `None` for nodes it cannot reach.
+    pub distances: Vec<Option<usize>>,
+    /// The node each one was first reached from; `None` for the source and
+    /// for unreachable nodes.
+    pub predecessors: Vec<Option<usize>>,
+}
+
+impl BfsTree {
+    /// A shortest path from the source to `node`, both included.
+    pub fn path_to(&self, node: usize) -> Option<Vec<usize>> {
+        self.distances.get(node).copied().flatten()?;
+
+        let mut path = vec![node];
+        let mut current = node;
+        while let Some(previous) = self.predecessors[current] {
+            path.push(previous);
+            current = previous;
+        }
+        path.reverse();
+        Some(path)
+    }
+}
+
+/// Runs a breadth-first search from `from` over the whole graph.
+///
+/// # Panics
+/// Panics if `from` or an edge points past the end of `graph`.
+pub fn bfs_tree(graph: &[Vec<usize>], from: usize) -> BfsTree {
+    assert!(from < graph.len(), "node out of range");
+    let mut distances = vec![None; graph.len()];
+    let mut predecessors = vec![None; graph.len()];
+    let mut queue = VecDeque::new();
+
+    distances[from] = Some(0);
+    queue.push_back(from);
+
+    while let Some(cur) = queue.pop_front() {
+        let next_distance = distances[cur].map(|d| d + 1);
+        for &n in &graph[cur] {
+            let seen = distances.get_mut(n).expect("neighbors out of range");
+            if seen.is_none() {
+                *seen = next_distance;
+                predecessors[n] = Some(cur);
+                queue.push_back(n);
+            }
+        }
+    }
+
+    BfsTree {
+        source: from,
+        distances,
+        predecessors,
+    }
+}
+
+/// Like [`shortest_path`](crate::shortest_path), but returns the nodes on
+/// the path, `from` and `to` included.
+pub fn shortest_path_route(graph: &[Vec<usize>], from: usize, to: usize) -> Option<Vec<usize>> {
+    bfs_tree(graph, from).path_to(to)
+}
+
+/// Every shortest path from `from` to `to`, in lexicographic order. There
+/// can be exponentially many.
+pub fn all_shortest_paths(graph: &[Vec<usize>], from: usize, to: usize) -> Vec<Vec<usize>> {
+    let tree = bfs_tree(graph, from);
+    if tree.distances.get(to).copied().flatten().is_none() {
+        return Vec::new();
+    }
+
+    // every node one hop closer that leads to each node, not just the first
+    let mut parents = vec![Vec::new(); graph.len()];
+    for (cur, neighbors) in graph.iter().enumerate() {
+        let Some(distance) = tree.distances[cur] else {
+            continue;
+        };
+        for &n in neighbors {
+            if tree.distances[n] == Some(distance + 1) && !parents[n].contains(&cur) {
+                parents[n].push(cur);
+            }
+        }
+    }
+
+    let mut paths = Vec::new();
+    collect_paths(&parents, to, &mut vec![to], &mut paths);
+    paths.sort();
+    paths
+}
+
+// Walks `parents` back from `node` to the source, which has none, recording
+// each complete path.
+fn collect_paths(
+    parents: &[Vec<usize>],
+    node: usize,
+    suffix: &mut Vec<usize>,
+    paths: &mut Vec<Vec<usize>>,
+) {
+    if parents[node].is_empty() {
+        paths.push(suffix.iter().rev().copied().collect());
+        return;
+    }
+    for &parent in &parents[node] {
+        suffix.push(parent);
+        collect_paths(parents, parent, suffix, paths);
+        suffix.pop();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::shortest_path;
+    use test_util::Rng;
+
+    //     1 --- 3
+    //    / \     \
+    //   0   2 --- 4
+    fn sample() -> Vec<Vec<usize>> {
+        vec![vec![1], vec![0, 2, 3], vec![1, 4], vec![1, 4], vec![2, 3]]
+    }
+
+    #[test]
+    fn test_shortest_path_route() {
+        let graph = sample();
+        let route = shortest_path_route(&graph, 0, 4).unwrap();
+        assert_eq!(route.len(), 4);
+        assert_eq!((route[0], route[3]), (0, 4));
+
+        assert_eq!(shortest_path_route(&graph, 2, 2), Some(vec![2]));
+
+        let disconnected = vec![vec![1], vec![0], vec![3], vec![2]];
+        assert_eq!(shortest_path_route(&disconnected, 0, 3), None);
+    }
+
+    #[test]
+    fn test_bfs_tree() {
+        let tree = bfs_tree(&sample(), 0);
+        assert_eq!(
+            tree.distances,
+            vec![Some(0), Some(1), Some(2), Some(2), Some(3)]
+        );
+        assert_eq!(tree.predecessors[0], None);
+        assert_eq!(tree.predecessors[1], Some(0));
+        assert_eq!(tree.predecessors[3], Some(1));
+        assert_eq!(tree.path_to(3), Some(vec![0, 1, 3]));
+        assert_eq!(tree.path_to(9), None);
+    }
+
+    #[test]
+    fn test_all_shortest_paths() {
+        let graph = sample();
+        assert_eq!(
+            all_shortest_paths(&graph, 0, 4),
+            vec![vec![0, 1, 2, 4], vec![0, 1, 3, 4]]
+        );
+        assert_eq!(all_shortest_paths(&graph, 4, 4), vec![vec![4]]);
+
+        // a square has two ways round
+        let square = vec![vec![1, 3], vec![0, 2], vec![1, 3], vec![2, 0]];
+        assert_eq!(
+            all_shortest_paths(&square, 0, 2),
+            vec![vec![0, 1, 2], vec![0, 3, 2]]
+        );
+
+        let disconnected = vec![vec![1], vec![0], vec![]];
+        assert!(all_shortest_paths(&disconnected, 0, 2).is_empty());
+    }
+
+    #[test]
+    fn test_parallel_edges_count_once() {
+        let graph = vec![vec![1, 1], vec![]];
+        assert_eq!(all_shortest_paths(&graph, 0, 1), vec![vec![0, 1]]);
+    }
+
+    #[test]
+    #[should_panic(expected = "node out of range")]
+    fn test_source_out_of_range() {
+        bfs_tree(&sample(), 5);
+    }
+
+    #[test]
+    fn test_matches_shortest_path() {
+        let mut rng = Rng(0xfeed_face_dead_beef);
+
+        for _ in 0..100 {
+            let nodes = rng.below(10) + 1;
+            let mut graph = vec![Vec::new(); nodes];
+            for _ in 0..rng.below(25) {
+                let (a, b) = (rng.below(nodes), rng.below(nodes));
+                graph[a].push(b);
+            }
+            let (from, to) = (rng.below(nodes), rng.below(nodes));
+
+            let hops = shortest_path(graph.clone(), from, to);
+            let route = shortest_path_route(&graph, from, to);
+            assert_eq!(route.as_ref().map(|r| r.len() - 1), hops);
+
+            let paths = all_shortest_paths(&graph, from, to);
+            assert_eq!(paths.is_empty(), hops.is_none());
+            if let Some(route) = route {
+                assert!(paths.contains(&route));
+            }
+            for path in &paths {
+                assert_eq!(Some(path.len() - 1), hops);
+                assert!(
+                    path.windows(2)
+                        .all(|step| graph[step[0]].contains(&step[1]))
+                );
+            }
+            let mut unique = paths.clone();
+            unique.dedup();
+            assert_eq!(unique.len(), paths.len());
+        }
+    }
+}
@@ -2,6 +2,12 @@
 
 use std::collections::{HashMap, VecDeque};
 
+mod bfs;
+mod weighted;
+
+pub use bfs::{BfsTree, all_shortest_paths, bfs_tree, shortest_path_route};
+pub use weighted::Graph;
+
 pub fn shortest_path(graph: Vec<Vec<usize>>, from: usize, to: usize) -> Option<usize> {
     // Handle same node case
     if from == to {
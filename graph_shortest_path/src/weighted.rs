@@ -0,0 +1,247 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+use std::ops::Add;
+
+/// Directed graph over nodes `0..node_count()` with weighted edges.
+///
+/// Weights must be non-negative for Dijkstra's algorithm, which
+/// [`add_edge`](Graph::add_edge) checks against `W::default()` as zero.
+#[derive(Debug, Clone)]
+pub struct Graph<W> {
+    adjacency: Vec<Vec<(usize, W)>>,
+}
+
+impl<W> Graph<W>
+where
+    W: Copy + Ord + Add<Output = W> + Default,
+{
+    pub fn new(nodes: usize) -> Self {
+        Graph {
+            adjacency: (0..nodes).map(|_| Vec::new()).collect(),
+        }
+    }
+
+    pub fn node_count(&self) -> usize {
+        self.adjacency.len()
+    }
+
+    /// Adds a node with no edges and returns its index.
+    pub fn add_node(&mut self) -> usize {
+        self.adjacency.push(Vec::new());
+        self.adjacency.len() - 1
+    }
+
+    /// # Panics
+    /// Panics if either node is out of range or `weight` is negative.
+    pub fn add_edge(&mut self, from: usize, to: usize, weight: W) {
+        assert!(to < self.node_count(), "node out of range");
+        assert!(weight >= W::default(), "edge weights must be non-negative");
+        self.adjacency
+            .get_mut(from)
+            .expect("node out of range")
+            .push((to, weight));
+    }
+
+    /// Adds `a -> b` and `b -> a` with the same weight.
+    pub fn add_undirected_edge(&mut self, a: usize, b: usize, weight: W) {
+        self.add_edge(a, b, weight);
+        self.add_edge(b, a, weight);
+    }
+
+    /// Outgoing edges of `node` as `(to, weight)`.
+    pub fn neighbors(&self, node: usize) -> &[(usize, W)] {
+        self.adjacency.get(node).expect("node out of range")
+    }
+
+    /// The cheapest route from `from` to `to` with its total weight, or
+    /// `None` if `to` cannot be reached. The route starts with `from` and
+    /// ends with `to`.
+    pub fn dijkstra(&self, from: usize, to: usize) -> Option<(W, Vec<usize>)> {
+        assert!(to < self.node_count(), "node out of range");
+        let (distances, parents) = self.search(from, Some(to));
+        let distance = distances[to]?;
+
+        let mut path = vec![to];
+        let mut node = to;
+        while let Some(parent) = parents[node] {
+            path.push(parent);
+            node = parent;
+        }
+        path.reverse();
+        Some((distance, path))
+    }
+
+    /// Distances from `from` to every node it can reach, itself included.
+    pub fn distances(&self, from: usize) -> HashMap<usize, W> {
+        let (distances, _) = self.search(from, None);
+        distances
+            .into_iter()
+            .enumerate()
+            .filter_map(|(node, distance)| Some((node, distance?)))
+            .collect()
+    }
+
+    // Dijkstra from `from`, stopping once `target` is settled. Returns the
+    // distance and the previous node on the cheapest route for every node
+    // reached.
+    fn search(&self, from: usize, target: Option<usize>) -> (Vec<Option<W>>, Vec<Option<usize>>) {
+        assert!(from < self.node_count(), "node out of range");
+        let mut distances = vec![None; self.node_count()];
+        let mut parents = vec![None; self.node_count()];
+        let mut heap = BinaryHeap::new();
+
+        distances[from] = Some(W::default());
+        heap.push(Reverse((W::default(), from)));
+
+        while let Some(Reverse((distance, node))) = heap.pop() {
+            // a cheaper route to `node` was already settled
+            if distances[node].is_some_and(|best| distance > best) {
+                continue;
+            }
+            if Some(node) == target {
+                break;
+            }
+
+            for &(next, weight) in &self.adjacency[node] {
+                let candidate = distance + weight;
+                if distances[next].is_none_or(|best| candidate < best) {
+                    distances[next] = Some(candidate);
+                    parents[next] = Some(node);
+                    heap.push(Reverse((candidate, next)));
+                }
+            }
+        }
+
+        (distances, parents)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use test_util::Rng;
+
+    //     1 --7-- 3
+    //   2/ \1    /1
+    //   0   2 -3- 4
+    //    \--9----/
+    fn sample() -> Graph<u32> {
+        let mut graph = Graph::new(5);
+        graph.add_undirected_edge(0, 1, 2);
+        graph.add_undirected_edge(1, 2, 1);
+        graph.add_undirected_edge(1, 3, 7);
+        graph.add_undirected_edge(2, 4, 3);
+        graph.add_undirected_edge(3, 4, 1);
+        graph.add_undirected_edge(0, 4, 9);
+        graph
+    }
+
+    #[test]
+    fn test_dijkstra_prefers_cheaper_over_fewer_hops() {
+        let graph = sample();
+        assert_eq!(graph.dijkstra(0, 4), Some((6, vec![0, 1, 2, 4])));
+        assert_eq!(graph.dijkstra(0, 3), Some((7, vec![0, 1, 2, 4, 3])));
+    }
+
+    #[test]
+    fn test_dijkstra_same_node_and_unreachable() {
+        let mut graph = sample();
+        assert_eq!(graph.dijkstra(2, 2), Some((0, vec![2])));
+
+        let lonely = graph.add_node();
+        assert_eq!(graph.dijkstra(0, lonely), None);
+        assert_eq!(graph.node_count(), 6);
+    }
+
+    #[test]
+    fn test_directed_edges() {
+        let mut graph = Graph::new(3);
+        graph.add_edge(0, 1, 1u64);
+        graph.add_edge(1, 2, 1);
+        assert_eq!(graph.dijkstra(0, 2), Some((2, vec![0, 1, 2])));
+        assert_eq!(graph.dijkstra(2, 0), None);
+        assert_eq!(graph.neighbors(0), &[(1, 1)]);
+    }
+
+    #[test]
+    fn test_distances() {
+        let mut graph = sample();
+        graph.add_node();
+
+        let distances = graph.distances(0);
+        let expected = HashMap::from([(0, 0), (1, 2), (2, 3), (3, 7), (4, 6)]);
+        assert_eq!(distances, expected);
+    }
+
+    #[test]
+    #[should_panic(expected = "edge weights must be non-negative")]
+    fn test_negative_weight() {
+        let mut graph = Graph::new(2);
+        graph.add_edge(0, 1, -1i32);
+    }
+
+    #[test]
+    #[should_panic(expected = "node out of range")]
+    fn test_edge_to_missing_node() {
+        let mut graph = Graph::new(2);
+        graph.add_edge(0, 2, 1u32);
+    }
+
+    #[test]
+    fn test_matches_floyd_warshall() {
+        let mut rng = Rng(0x0123_4567_89ab_cdef);
+
+        for _ in 0..50 {
+            let nodes = rng.below(12) + 1;
+            let mut graph = Graph::new(nodes);
+            let mut table = vec![vec![None; nodes]; nodes];
+            for (node, row) in table.iter_mut().enumerate() {
+                row[node] = Some(0);
+            }
+            for _ in 0..rng.below(40) {
+                let (a, b, weight) = (rng.below(nodes), rng.below(nodes), rng.below(20) as u64);
+                graph.add_edge(a, b, weight);
+                if table[a][b].is_none_or(|w| weight < w) {
+                    table[a][b] = Some(weight);
+                }
+            }
+
+            for k in 0..nodes {
+                for i in 0..nodes {
+                    for j in 0..nodes {
+                        if let (Some(ik), Some(kj)) = (table[i][k], table[k][j])
+                            && table[i][j].is_none_or(|ij| ik + kj < ij)
+                        {
+                            table[i][j] = Some(ik + kj);
+                        }
+                    }
+                }
+            }
+
+            for (from, row) in table.iter().enumerate() {
+                let distances = graph.distances(from);
+                for (to, &expected) in row.iter().enumerate() {
+                    assert_eq!(distances.get(&to).copied(), expected);
+
+                    // the route has the reported cost and uses real edges
+                    if let Some((distance, path)) = graph.dijkstra(from, to) {
+                        let cost: u64 = path
+                            .windows(2)
+                            .map(|step| {
+                                graph
+                                    .neighbors(step[0])
+                                    .iter()
+                                    .filter(|&&(next, _)| next == step[1])
+                                    .map(|&(_, w)| w)
+                                    .min()
+                                    .expect("path follows an edge")
+                            })
+                            .sum();
+                        assert_eq!(cost, distance);
+                        assert_eq!(Some(distance), expected);
+                    }
+                }
+            }
+        }
+    }
+}
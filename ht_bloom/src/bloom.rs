@@ -0,0 +1,155 @@
+use std::hash::{BuildHasher, Hash};
+
+use ht_vanila::hashers::{DefaultBuildHasher, double_hash, hash_key};
+
+use crate::{false_positive_rate, optimal_size};
+
+/// A Bloom filter: `k` bits set per item in a fixed bit array.
+#[derive(Clone)]
+pub struct BloomFilter<S = DefaultBuildHasher> {
+    bits: Vec<u64>,
+    // number of usable bits; the last word may have spare ones
+    len: usize,
+    hashes: usize,
+    items: usize,
+    hash_builder: S,
+}
+
+impl BloomFilter<DefaultBuildHasher> {
+    /// Sized so the false positive rate stays near `false_positive_rate`
+    /// until `expected_items` items are in.
+    ///
+    /// # Panics
+    /// Panics unless `0 < false_positive_rate < 1`.
+    pub fn new(expected_items: usize, false_positive_rate: f64) -> Self {
+        Self::with_hasher(
+            expected_items,
+            false_positive_rate,
+            DefaultBuildHasher::default(),
+        )
+    }
+}
+
+impl<S> BloomFilter<S>
+where
+    S: BuildHasher,
+{
+    /// # Panics
+    /// Panics unless `0 < false_positive_rate < 1`.
+    pub fn with_hasher(expected_items: usize, false_positive_rate: f64, hash_builder: S) -> Self {
+        let (len, hashes) = optimal_size(expected_items, false_positive_rate);
+        BloomFilter {
+            bits: vec![0; len.div_ceil(64)],
+            len,
+            hashes,
+            items: 0,
+            hash_builder,
+        }
+    }
+
+    pub fn bits(&self) -> usize {
+        self.len
+    }
+
+    pub fn hashes(&self) -> usize {
+        self.hashes
+    }
+
+    /// Inserts so far, counting repeats.
+    pub fn items(&self) -> usize {
+        self.items
+    }
+
+    pub fn insert<T>(&mut self, item: &T)
+    where
+        T: Hash + ?Sized,
+    {
+        let hash = hash_key(&self.hash_builder, item);
+        for index in double_hash(hash, self.hashes, self.len) {
+            self.bits[index / 64] |= 1 << (index % 64);
+        }
+        self.items += 1;
+    }
+
+    /// `false` means `item` was never inserted; `true` means it probably was.
+    pub fn contains<T>(&self, item: &T) -> bool
+    where
+        T: Hash + ?Sized,
+    {
+        let hash = hash_key(&self.hash_builder, item);
+        double_hash(hash, self.hashes, self.len)
+            .all(|index| self.bits[index / 64] & (1 << (index % 64)) != 0)
+    }
+
+    /// The false positive rate expected after the inserts so far.
+    pub fn estimated_false_positive_rate(&self) -> f64 {
+        false_positive_rate(self.len, self.hashes, self.items)
+    }
+
+    pub fn clear(&mut self) {
+        self.bits.fill(0);
+        self.items = 0;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // Inserts `items` keys and measures how often 100k other keys hit.
+    fn measured_rate(items: usize, rate: f64) -> f64 {
+        let mut filter = BloomFilter::new(items, rate);
+        for key in 0..items {
+            filter.insert(&key);
+        }
+
+        // no false negatives, ever
+        assert!((0..items).all(|key| filter.contains(&key)));
+
+        let probes = 100_000;
+        let hits = (items..items + probes)
+            .filter(|key| filter.contains(key))
+            .count();
+        hits as f64 / probes as f64
+    }
+
+    #[test]
+    fn test_false_positive_rate_is_within_bounds() {
+        for rate in [0.1, 0.01, 0.001] {
+            let measured = measured_rate(10_000, rate);
+            assert!(measured < rate * 1.5, "{} for {}", measured, rate);
+        }
+    }
+
+    #[test]
+    fn test_estimate_follows_inserts() {
+        let mut filter = BloomFilter::new(1000, 0.01);
+        assert_eq!(filter.estimated_false_positive_rate(), 0.0);
+
+        for key in 0..1000 {
+            filter.insert(&key);
+        }
+        let estimate = filter.estimated_false_positive_rate();
+        assert!((0.005..0.02).contains(&estimate), "{}", estimate);
+        assert_eq!(filter.items(), 1000);
+
+        // overfilling degrades it
+        for key in 1000..5000 {
+            filter.insert(&key);
+        }
+        assert!(filter.estimated_false_positive_rate() > 0.3);
+    }
+
+    #[test]
+    fn test_unsized_items_and_clear() {
+        let mut filter = BloomFilter::new(100, 0.01);
+        filter.insert("apple");
+        assert!(filter.contains("apple"));
+        assert!(filter.contains(&String::from("apple")));
+        assert!(!filter.contains("pear"));
+
+        filter.clear();
+        assert!(!filter.contains("apple"));
+        assert_eq!(filter.items(), 0);
+    }
+}
@@ -0,0 +1,207 @@
+use std::hash::{BuildHasher, Hash};
+
+use ht_vanila::hashers::{DefaultBuildHasher, double_hash, hash_key};
+
+use crate::{false_positive_rate, optimal_size};
+
+/// A Bloom filter with a small counter per slot instead of a bit, so items
+/// can be removed.
+///
+/// A counter that reaches `u8::MAX` stays there: it can no longer tell how
+/// many items share it, and decrementing it could cause false negatives.
+#[derive(Clone)]
+pub struct CountingBloomFilter<S = DefaultBuildHasher> {
+    counters: Vec<u8>,
+    hashes: usize,
+    items: usize,
+    hash_builder: S,
+}
+
+impl CountingBloomFilter<DefaultBuildHasher> {
+    /// # Panics
+    /// Panics unless `0 < false_positive_rate < 1`.
+    pub fn new(expected_items: usize, false_positive_rate: f64) -> Self {
+        Self::with_hasher(
+            expected_items,
+            false_positive_rate,
+            DefaultBuildHasher::default(),
+        )
+    }
+}
+
+impl<S> CountingBloomFilter<S>
+where
+    S: BuildHasher,
+{
+    /// # Panics
+    /// Panics unless `0 < false_positive_rate < 1`.
+    pub fn with_hasher(expected_items: usize, false_positive_rate: f64, hash_builder: S) -> Self {
+        let (len, hashes) = optimal_size(expected_items, false_positive_rate);
+        CountingBloomFilter {
+            counters: vec![0; len],
+            hashes,
+            items: 0,
+            hash_builder,
+        }
+    }
+
+    pub fn counters(&self) -> usize {
+        self.counters.len()
+    }
+
+    pub fn hashes(&self) -> usize {
+        self.hashes
+    }
+
+    /// Inserts minus removes so far.
+    pub fn items(&self) -> usize {
+        self.items
+    }
+
+    pub fn insert<T>(&mut self, item: &T)
+    where
+        T: Hash + ?Sized,
+    {
+        for index in self.indexes(item) {
+            let counter = &mut self.counters[index];
+            *counter = counter.saturating_add(1);
+        }
+        self.items += 1;
+    }
+
+    /// `false` means `item` is not in the filter; `true` means it probably
+    /// is.
+    pub fn contains<T>(&self, item: &T) -> bool
+    where
+        T: Hash + ?Sized,
+    {
+        self.indexes(item).all(|index| self.counters[index] > 0)
+    }
+
+    /// Removes one insert of `item`, returning `false` if it is certainly
+    /// not in the filter.
+    ///
+    /// Only remove items that were inserted: removing a false positive
+    /// takes counts from other items and can make them look absent.
+    pub fn remove<T>(&mut self, item: &T) -> bool
+    where
+        T: Hash + ?Sized,
+    {
+        if !self.contains(item) {
+            return false;
+        }
+        for index in self.indexes(item) {
+            let counter = &mut self.counters[index];
+            if *counter < u8::MAX {
+                *counter -= 1;
+            }
+        }
+        // a stuck counter can keep `item` present after its last insert
+        // is gone
+        self.items = self.items.saturating_sub(1);
+        true
+    }
+
+    /// The false positive rate expected for the items currently in.
+    pub fn estimated_false_positive_rate(&self) -> f64 {
+        false_positive_rate(self.counters.len(), self.hashes, self.items)
+    }
+
+    pub fn clear(&mut self) {
+        self.counters.fill(0);
+        self.items = 0;
+    }
+
+    fn indexes<T>(&self, item: &T) -> impl Iterator<Item = usize> + use<T, S>
+    where
+        T: Hash + ?Sized,
+    {
+        let hash = hash_key(&self.hash_builder, item);
+        double_hash(hash, self.hashes, self.counters.len())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_insert_and_remove() {
+        let mut filter = CountingBloomFilter::new(100, 0.01);
+        filter.insert("a");
+        filter.insert("a");
+        filter.insert("b");
+
+        assert!(filter.remove("a"));
+        assert!(filter.contains("a"));
+        assert!(filter.remove("a"));
+        assert!(!filter.contains("a"));
+        assert!(!filter.remove("a"));
+
+        assert!(filter.contains("b"));
+        assert_eq!(filter.items(), 1);
+    }
+
+    #[test]
+    fn test_error_bounds_after_removals() {
+        let items = 10_000;
+        let rate = 0.01;
+        let mut filter = CountingBloomFilter::new(items, rate);
+        for key in 0..items {
+            filter.insert(&key);
+        }
+        for key in 0..items / 2 {
+            assert!(filter.remove(&key));
+        }
+
+        // the kept half never goes missing
+        assert!((items / 2..items).all(|key| filter.contains(&key)));
+
+        // removed keys look present only as often as any absent key, which
+        // at half load is well under the configured rate
+        let still_present = (0..items / 2).filter(|key| filter.contains(key)).count();
+        assert!(
+            (still_present as f64) < (items / 2) as f64 * rate,
+            "{}",
+            still_present
+        );
+
+        let probes = 100_000;
+        let hits = (items..items + probes)
+            .filter(|key| filter.contains(key))
+            .count();
+        let measured = hits as f64 / probes as f64;
+        assert!(measured < rate, "{}", measured);
+        assert!(filter.estimated_false_positive_rate() < rate);
+    }
+
+    #[test]
+    fn test_saturated_counters_stay_put() {
+        let mut filter = CountingBloomFilter::new(10, 0.1);
+        for _ in 0..300 {
+            filter.insert("hot");
+        }
+        filter.insert("cold");
+        for _ in 0..300 {
+            filter.remove("hot");
+        }
+
+        // "hot" is stuck, and nothing sharing its counters was lost
+        assert!(filter.contains("hot"));
+        assert!(filter.contains("cold"));
+    }
+
+    #[test]
+    fn test_removing_past_saturation_keeps_items_at_zero() {
+        let mut filter = CountingBloomFilter::new(10, 0.1);
+        for _ in 0..300 {
+            filter.insert("hot");
+        }
+        for _ in 0..300 {
+            assert!(filter.remove("hot"));
+        }
+
+        assert!(filter.remove("hot"));
+        assert_eq!(filter.items(), 0);
+    }
+}
@@ -0,0 +1,60 @@
+//! Set membership in bounded memory. A query can wrongly answer "present"
+//! at a configurable rate, but never wrongly answers "absent".
+//!
+//! Each filter hashes an item once through [`hash_key`], defaulting to
+//! [`DefaultBuildHasher`] so filters built apart agree on positions, and
+//! derives its k positions from that hash with [`double_hash`].
+//!
+//! [`hash_key`]: ht_vanila::hashers::hash_key
+//! [`DefaultBuildHasher`]: ht_vanila::hashers::DefaultBuildHasher
+//! [`double_hash`]: ht_vanila::hashers::double_hash
+
+use std::f64::consts::LN_2;
+
+mod bloom;
+mod counting;
+
+pub use bloom::BloomFilter;
+pub use counting::CountingBloomFilter;
+
+// Slot and hash counts that reach `false_positive_rate` once `expected_items`
+// items are in: m = -n ln p / (ln 2)^2 and k = (m / n) ln 2.
+fn optimal_size(expected_items: usize, false_positive_rate: f64) -> (usize, usize) {
+    assert!(
+        false_positive_rate > 0.0 && false_positive_rate < 1.0,
+        "false positive rate must be between 0 and 1"
+    );
+    let items = expected_items.max(1) as f64;
+    let slots = (-items * false_positive_rate.ln() / (LN_2 * LN_2)).ceil();
+    let hashes = (slots / items * LN_2).round().max(1.0);
+    (slots as usize, hashes as usize)
+}
+
+// Chance that all `hashes` slots of an absent item are set after `items`
+// inserts into `slots` slots: (1 - e^(-kn/m))^k.
+fn false_positive_rate(slots: usize, hashes: usize, items: usize) -> f64 {
+    let k = hashes as f64;
+    (1.0 - (-k * items as f64 / slots as f64).exp()).powf(k)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_optimal_size() {
+        // the textbook figures: ~9.6 bits and 7 hashes per item for 1%
+        let (slots, hashes) = optimal_size(1000, 0.01);
+        assert_eq!(slots, 9586);
+        assert_eq!(hashes, 7);
+
+        let rate = false_positive_rate(slots, hashes, 1000);
+        assert!((0.009..0.011).contains(&rate), "{}", rate);
+    }
+
+    #[test]
+    #[should_panic(expected = "false positive rate must be between 0 and 1")]
+    fn test_invalid_rate() {
+        optimal_size(10, 1.0);
+    }
+}
@@ -0,0 +1,394 @@
+use std::{hash::Hash, mem};
+
+use ht_vanila::HashTable;
+use linkedlist::{Handle, LinkedList};
+
+use crate::{CacheStats, EvictionCallback};
+
+/// Least-frequently-used cache with O(1) `get`, `put` and eviction.
+///
+/// Entries are grouped in buckets by use count. A hit moves the entry from
+/// its bucket to the next one; eviction takes the least recently used entry
+/// of the lowest bucket.
+pub struct LfuCache<K, V>
+where
+    K: Hash + Eq,
+{
+    // key -> (use count, entry in that count's bucket)
+    map: HashTable<K, (u64, Handle<(K, V)>)>,
+    // use count -> index into `lists` of the entries with that count; empty
+    // buckets are removed
+    buckets: HashTable<u64, usize>,
+    // bucket storage, most recently used entry at the front; lists of removed
+    // buckets are parked in `free` for reuse
+    lists: Vec<LinkedList<(K, V)>>,
+    free: Vec<usize>,
+    // lowest count with a bucket, exact whenever the cache is full
+    min_count: u64,
+    len: usize,
+    capacity: usize,
+    stats: CacheStats,
+    on_evict: Option<EvictionCallback<K, V>>,
+}
+
+impl<K, V> LfuCache<K, V>
+where
+    K: Hash + Eq + Clone,
+{
+    /// # Panics
+    /// Panics if `capacity` is 0.
+    pub fn new(capacity: usize) -> Self {
+        assert!(capacity > 0, "capacity must be greater than zero");
+        LfuCache {
+            map: HashTable::new(capacity),
+            buckets: HashTable::new(8),
+            lists: Vec::new(),
+            free: Vec::new(),
+            min_count: 0,
+            len: 0,
+            capacity,
+            stats: CacheStats::default(),
+            on_evict: None,
+        }
+    }
+
+    /// Calls `f` with every entry evicted to make room.
+    pub fn on_evict<F>(mut self, f: F) -> Self
+    where
+        F: FnMut(K, V) + 'static,
+    {
+        self.on_evict = Some(Box::new(f));
+        self
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn stats(&self) -> CacheStats {
+        self.stats
+    }
+
+    pub fn contains(&self, key: &K) -> bool {
+        self.map.get(key).is_some()
+    }
+
+    /// How many times `key` was used since it was inserted, counting the
+    /// insert itself.
+    pub fn frequency(&self, key: &K) -> Option<u64> {
+        self.map.get(key).map(|&(count, _)| count)
+    }
+
+    /// Looks up `key` and counts it as a use.
+    pub fn get(&mut self, key: &K) -> Option<&V> {
+        self.get_mut(key).map(|value| &*value)
+    }
+
+    pub fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+        match self.touch(key) {
+            Some((count, handle)) => {
+                self.stats.hits += 1;
+                Some(&mut self.entry_mut(count, handle).1)
+            }
+            None => {
+                self.stats.misses += 1;
+                None
+            }
+        }
+    }
+
+    /// Looks up `key` without counting a use or touching statistics.
+    pub fn peek(&self, key: &K) -> Option<&V> {
+        let &(count, handle) = self.map.get(key)?;
+        let &index = self.buckets.get(&count).expect("bucket of a cached key");
+        let bucket = &self.lists[index];
+        // SAFETY: `map` only holds handles into the bucket of their count.
+        Some(unsafe { &bucket.get_handle(handle).1 })
+    }
+
+    /// Inserts or updates `key`. Updating counts as a use; inserting a new
+    /// key evicts first if the cache is full. Returns the previous value.
+    pub fn put(&mut self, key: K, value: V) -> Option<V> {
+        if let Some((count, handle)) = self.touch(&key) {
+            let entry = self.entry_mut(count, handle);
+            return Some(mem::replace(&mut entry.1, value));
+        }
+
+        if self.len == self.capacity {
+            self.evict();
+        }
+        let handle = self.bucket_mut(1).push_front_handle((key.clone(), value));
+        self.map.insert(key, (1, handle));
+        self.min_count = 1;
+        self.len += 1;
+        None
+    }
+
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        let (count, handle) = self.map.remove(key)?;
+        // `min_count` may go stale here, but the cache is no longer full and
+        // the next insert resets it before any eviction reads it
+        self.len -= 1;
+        Some(self.take_entry(count, handle).1)
+    }
+
+    // Moves `key` to the bucket for one more use and returns its new count
+    // and handle.
+    fn touch(&mut self, key: &K) -> Option<(u64, Handle<(K, V)>)> {
+        let (count, handle) = *self.map.get(key)?;
+        let entry = self.take_entry(count, handle);
+        if count == self.min_count && self.buckets.get(&count).is_none() {
+            self.min_count += 1;
+        }
+
+        let count = count + 1;
+        let handle = self.bucket_mut(count).push_front_handle(entry);
+        self.map.insert(key.clone(), (count, handle));
+        Some((count, handle))
+    }
+
+    fn evict(&mut self) {
+        let bucket = match self.buckets.get(&self.min_count) {
+            Some(&index) => &self.lists[index],
+            None => return,
+        };
+        let handle = bucket.back_handle().expect("buckets are never empty");
+
+        let (key, value) = self.take_entry(self.min_count, handle);
+        self.map.remove(&key);
+        self.len -= 1;
+        self.stats.evictions += 1;
+
+        if let Some(on_evict) = &mut self.on_evict {
+            on_evict(key, value);
+        }
+    }
+
+    // Unlinks an entry from its bucket, dropping the bucket if it empties.
+    // The caller keeps `map` in sync.
+    fn take_entry(&mut self, count: u64, handle: Handle<(K, V)>) -> (K, V) {
+        let &index = self.buckets.get(&count).expect("bucket of a cached key");
+        let bucket = &mut self.lists[index];
+        // SAFETY: `map` only holds handles into the bucket of their count.
+        let entry = unsafe { bucket.remove_handle(handle) };
+        if bucket.is_empty() {
+            self.buckets.remove(&count);
+            self.free.push(index);
+        }
+        entry
+    }
+
+    fn entry_mut(&mut self, count: u64, handle: Handle<(K, V)>) -> &mut (K, V) {
+        let bucket = self.bucket_mut(count);
+        // SAFETY: `handle` was just pushed into this bucket by `touch`.
+        unsafe { bucket.get_handle_mut(handle) }
+    }
+
+    // The bucket for `count`, created from a parked list if missing.
+    fn bucket_mut(&mut self, count: u64) -> &mut LinkedList<(K, V)> {
+        let index = match self.buckets.get(&count) {
+            Some(&index) => index,
+            None => {
+                let index = self.free.pop().unwrap_or_else(|| {
+                    self.lists.push(LinkedList::new());
+                    self.lists.len() - 1
+                });
+                self.buckets.insert(count, index);
+                index
+            }
+        };
+        &mut self.lists[index]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{cell::RefCell, rc::Rc};
+
+    use super::*;
+    use test_util::Rng;
+
+    #[test]
+    fn test_evicts_least_frequently_used() {
+        let mut cache = LfuCache::new(2);
+        cache.put(1, 10);
+        cache.put(2, 20);
+        cache.get(&1);
+        cache.get(&1);
+        cache.get(&2);
+
+        cache.put(3, 30);
+        assert!(!cache.contains(&2));
+        assert_eq!(cache.frequency(&1), Some(3));
+        assert_eq!(cache.frequency(&3), Some(1));
+        assert_eq!(cache.len(), 2);
+    }
+
+    #[test]
+    fn test_ties_evict_least_recently_used() {
+        let mut cache = LfuCache::new(3);
+        cache.put(1, 10);
+        cache.put(2, 20);
+        cache.put(3, 30);
+        cache.get(&2);
+        cache.get(&1);
+
+        // 3 is alone at count 1
+        cache.put(4, 40);
+        assert!(!cache.contains(&3));
+
+        // 4 is now the only entry at count 1
+        cache.put(5, 50);
+        assert!(!cache.contains(&4));
+
+        // 5 at count 1 again
+        cache.get(&5);
+        cache.put(6, 60);
+        assert!(!cache.contains(&2));
+    }
+
+    #[test]
+    fn test_put_existing_key_counts_as_use() {
+        let mut cache = LfuCache::new(2);
+        cache.put("a", 1);
+        assert_eq!(cache.put("a", 2), Some(1));
+        assert_eq!(cache.frequency(&"a"), Some(2));
+        assert_eq!(cache.peek(&"a"), Some(&2));
+        assert_eq!(cache.len(), 1);
+    }
+
+    #[test]
+    fn test_remove_then_insert() {
+        let mut cache = LfuCache::new(2);
+        cache.put(1, 10);
+        cache.put(2, 20);
+        cache.get(&2);
+        assert_eq!(cache.remove(&1), Some(10));
+        assert_eq!(cache.remove(&1), None);
+
+        cache.put(3, 30);
+        cache.put(4, 40);
+        assert!(!cache.contains(&3));
+        assert!(cache.contains(&2));
+        assert_eq!(cache.stats().evictions, 1);
+    }
+
+    #[test]
+    fn test_stats_and_callback() {
+        let evicted = Rc::new(RefCell::new(Vec::new()));
+        let sink = evicted.clone();
+        let mut cache = LfuCache::new(1).on_evict(move |key, value| {
+            sink.borrow_mut().push((key, value));
+        });
+
+        cache.put(1, 10);
+        *cache.get_mut(&1).unwrap() += 1;
+        cache.get(&2);
+        cache.put(2, 20);
+
+        let stats = cache.stats();
+        assert_eq!((stats.hits, stats.misses, stats.evictions), (1, 1, 1));
+        assert_eq!(*evicted.borrow(), vec![(1, 11)]);
+    }
+
+    // Every entry with its use count and the tick of its last use.
+    struct Model {
+        entries: Vec<(i32, i32, u64, usize)>,
+        capacity: usize,
+        stats: CacheStats,
+        evicted: Vec<(i32, i32)>,
+    }
+
+    impl Model {
+        fn position(&self, key: i32) -> Option<usize> {
+            self.entries.iter().position(|entry| entry.0 == key)
+        }
+
+        fn get(&mut self, key: i32, tick: usize) -> Option<i32> {
+            match self.position(key) {
+                Some(index) => {
+                    self.stats.hits += 1;
+                    let entry = &mut self.entries[index];
+                    entry.2 += 1;
+                    entry.3 = tick;
+                    Some(entry.1)
+                }
+                None => {
+                    self.stats.misses += 1;
+                    None
+                }
+            }
+        }
+
+        fn put(&mut self, key: i32, value: i32, tick: usize) -> Option<i32> {
+            if let Some(index) = self.position(key) {
+                let entry = &mut self.entries[index];
+                entry.2 += 1;
+                entry.3 = tick;
+                return Some(mem::replace(&mut entry.1, value));
+            }
+
+            if self.entries.len() == self.capacity {
+                let victim = (0..self.entries.len())
+                    .min_by_key(|&i| (self.entries[i].2, self.entries[i].3))
+                    .unwrap();
+                let (key, value, _, _) = self.entries.remove(victim);
+                self.evicted.push((key, value));
+                self.stats.evictions += 1;
+            }
+            self.entries.push((key, value, 1, tick));
+            None
+        }
+
+        fn remove(&mut self, key: i32) -> Option<i32> {
+            let index = self.position(key)?;
+            Some(self.entries.remove(index).1)
+        }
+    }
+
+    #[test]
+    fn test_matches_model() {
+        let mut rng = Rng(0xfeed_f00d);
+        for _ in 0..50 {
+            let capacity = rng.below(6) + 1;
+            let evicted = Rc::new(RefCell::new(Vec::new()));
+            let sink = evicted.clone();
+            let mut cache = LfuCache::new(capacity).on_evict(move |key, value| {
+                sink.borrow_mut().push((key, value));
+            });
+            let mut model = Model {
+                entries: Vec::new(),
+                capacity,
+                stats: CacheStats::default(),
+                evicted: Vec::new(),
+            };
+
+            for tick in 0..300 {
+                let key = rng.below(10) as i32;
+                let value = tick as i32;
+                match rng.below(4) {
+                    0 | 1 => assert_eq!(cache.get(&key).copied(), model.get(key, tick)),
+                    2 => assert_eq!(cache.put(key, value), model.put(key, value, tick)),
+                    _ => assert_eq!(cache.remove(&key), model.remove(key)),
+                }
+
+                assert_eq!(cache.len(), model.entries.len());
+                for &(key, value, count, _) in &model.entries {
+                    assert_eq!(cache.peek(&key), Some(&value));
+                    assert_eq!(cache.frequency(&key), Some(count));
+                }
+            }
+
+            assert_eq!(cache.stats(), model.stats);
+            assert_eq!(*evicted.borrow(), model.evicted);
+        }
+    }
+}
@@ -0,0 +1,48 @@
+//! Bounded caches built on [`ht_vanila::HashTable`] and
+//! [`linkedlist::LinkedList`].
+
+mod lfu;
+mod lru;
+
+pub use lfu::LfuCache;
+pub use lru::LruCache;
+
+/// Counters kept by every cache.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct CacheStats {
+    pub hits: u64,
+    pub misses: u64,
+    /// Entries dropped to make room, not counting explicit removes.
+    pub evictions: u64,
+}
+
+impl CacheStats {
+    /// Fraction of lookups that were hits, or 0 before the first lookup.
+    pub fn hit_rate(&self) -> f64 {
+        let lookups = self.hits + self.misses;
+        if lookups == 0 {
+            return 0.0;
+        }
+        self.hits as f64 / lookups as f64
+    }
+}
+
+/// Called with every evicted entry.
+type EvictionCallback<K, V> = Box<dyn FnMut(K, V)>;
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_hit_rate() {
+        assert_eq!(CacheStats::default().hit_rate(), 0.0);
+
+        let stats = CacheStats {
+            hits: 3,
+            misses: 1,
+            evictions: 0,
+        };
+        assert_eq!(stats.hit_rate(), 0.75);
+    }
+}
@@ -0,0 +1,328 @@
+use std::{hash::Hash, mem};
+
+use ht_vanila::HashTable;
+use linkedlist::{Handle, LinkedList};
+
+use crate::{CacheStats, EvictionCallback};
+
+/// Least-recently-used cache with O(1) `get`, `put` and eviction.
+///
+/// The map points every key at its node in the recency list, so a hit only
+/// relinks that node to the front and eviction pops the back.
+pub struct LruCache<K, V>
+where
+    K: Hash + Eq,
+{
+    // invariant: a key is in `map` exactly when `order` holds its entry, and
+    // the handle points at that entry
+    map: HashTable<K, Handle<(K, V)>>,
+    // most recently used at the front
+    order: LinkedList<(K, V)>,
+    capacity: usize,
+    stats: CacheStats,
+    on_evict: Option<EvictionCallback<K, V>>,
+}
+
+impl<K, V> LruCache<K, V>
+where
+    K: Hash + Eq + Clone,
+{
+    /// # Panics
+    /// Panics if `capacity` is 0.
+    pub fn new(capacity: usize) -> Self {
+        assert!(capacity > 0, "capacity must be greater than zero");
+        LruCache {
+            map: HashTable::new(capacity),
+            order: LinkedList::new(),
+            capacity,
+            stats: CacheStats::default(),
+            on_evict: None,
+        }
+    }
+
+    /// Calls `f` with every entry evicted to make room.
+    pub fn on_evict<F>(mut self, f: F) -> Self
+    where
+        F: FnMut(K, V) + 'static,
+    {
+        self.on_evict = Some(Box::new(f));
+        self
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn len(&self) -> usize {
+        self.order.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.order.is_empty()
+    }
+
+    pub fn stats(&self) -> CacheStats {
+        self.stats
+    }
+
+    pub fn contains(&self, key: &K) -> bool {
+        self.map.get(key).is_some()
+    }
+
+    /// Looks up `key` and marks it as most recently used.
+    pub fn get(&mut self, key: &K) -> Option<&V> {
+        self.get_mut(key).map(|value| &*value)
+    }
+
+    pub fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+        let handle = match self.map.get(key) {
+            Some(&handle) => handle,
+            None => {
+                self.stats.misses += 1;
+                return None;
+            }
+        };
+
+        self.stats.hits += 1;
+        // SAFETY: by the invariant on `map`, `handle` is in `order`.
+        unsafe {
+            self.order.move_to_front(handle);
+            Some(&mut self.order.get_handle_mut(handle).1)
+        }
+    }
+
+    /// Looks up `key` without touching recency or statistics.
+    pub fn peek(&self, key: &K) -> Option<&V> {
+        let handle = *self.map.get(key)?;
+        // SAFETY: by the invariant on `map`, `handle` is in `order`.
+        Some(unsafe { &self.order.get_handle(handle).1 })
+    }
+
+    /// Inserts or updates `key` as the most recently used entry, evicting
+    /// the least recently used one if the cache is full. Returns the previous
+    /// value for `key`.
+    pub fn put(&mut self, key: K, value: V) -> Option<V> {
+        if let Some(&handle) = self.map.get(&key) {
+            // SAFETY: by the invariant on `map`, `handle` is in `order`.
+            return unsafe {
+                self.order.move_to_front(handle);
+                Some(mem::replace(
+                    &mut self.order.get_handle_mut(handle).1,
+                    value,
+                ))
+            };
+        }
+
+        if self.order.len() == self.capacity {
+            self.evict();
+        }
+        let handle = self.order.push_front_handle((key.clone(), value));
+        self.map.insert(key, handle);
+        None
+    }
+
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        let handle = self.map.remove(key)?;
+        // SAFETY: `handle` was in `map`, so it is in `order`; it is dropped
+        // from both together.
+        Some(unsafe { self.order.remove_handle(handle).1 })
+    }
+
+    /// Entries from most to least recently used.
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &V)> {
+        self.order.iter().map(|(key, value)| (key, value))
+    }
+
+    fn evict(&mut self) {
+        let handle = match self.order.back_handle() {
+            Some(handle) => handle,
+            None => return,
+        };
+
+        // SAFETY: `handle` was just read from `order`.
+        let (key, value) = unsafe { self.order.remove_handle(handle) };
+        self.map.remove(&key);
+        self.stats.evictions += 1;
+
+        if let Some(on_evict) = &mut self.on_evict {
+            on_evict(key, value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{cell::RefCell, rc::Rc};
+
+    use super::*;
+    use test_util::Rng;
+
+    fn keys(cache: &LruCache<i32, i32>) -> Vec<i32> {
+        cache.iter().map(|(key, _)| *key).collect()
+    }
+
+    #[test]
+    fn test_evicts_least_recently_used() {
+        let mut cache = LruCache::new(2);
+        cache.put(1, 10);
+        cache.put(2, 20);
+        assert_eq!(cache.get(&1), Some(&10));
+
+        cache.put(3, 30);
+        assert!(!cache.contains(&2));
+        assert_eq!(keys(&cache), vec![3, 1]);
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache.stats().evictions, 1);
+    }
+
+    #[test]
+    fn test_put_existing_key_updates_without_evicting() {
+        let mut cache = LruCache::new(2);
+        cache.put(1, 10);
+        cache.put(2, 20);
+        assert_eq!(cache.put(1, 11), Some(10));
+
+        assert_eq!(keys(&cache), vec![1, 2]);
+        assert_eq!(cache.peek(&1), Some(&11));
+        assert_eq!(cache.stats().evictions, 0);
+    }
+
+    #[test]
+    fn test_peek_does_not_touch() {
+        let mut cache = LruCache::new(2);
+        cache.put(1, 10);
+        cache.put(2, 20);
+        assert_eq!(cache.peek(&1), Some(&10));
+        assert_eq!(cache.stats(), CacheStats::default());
+
+        cache.put(3, 30);
+        assert_eq!(cache.peek(&1), None);
+    }
+
+    #[test]
+    fn test_stats() {
+        let mut cache = LruCache::new(1);
+        cache.put("a", 1);
+        cache.get(&"a");
+        cache.get(&"b");
+        *cache.get_mut(&"a").unwrap() += 1;
+        cache.put("b", 2);
+
+        let stats = cache.stats();
+        assert_eq!((stats.hits, stats.misses, stats.evictions), (2, 1, 1));
+        assert_eq!(cache.capacity(), 1);
+    }
+
+    #[test]
+    fn test_remove() {
+        let mut cache = LruCache::new(2);
+        cache.put(1, 10);
+        assert_eq!(cache.remove(&1), Some(10));
+        assert_eq!(cache.remove(&1), None);
+        assert!(cache.is_empty());
+
+        // an explicit remove is not an eviction
+        assert_eq!(cache.stats().evictions, 0);
+    }
+
+    #[test]
+    fn test_eviction_callback() {
+        let evicted = Rc::new(RefCell::new(Vec::new()));
+        let sink = evicted.clone();
+        let mut cache = LruCache::new(2).on_evict(move |key, value| {
+            sink.borrow_mut().push((key, value));
+        });
+
+        for key in 0..5 {
+            cache.put(key, key * 10);
+        }
+        cache.remove(&4);
+        assert_eq!(*evicted.borrow(), vec![(0, 0), (1, 10), (2, 20)]);
+    }
+
+    #[test]
+    #[should_panic(expected = "capacity must be greater than zero")]
+    fn test_zero_capacity() {
+        LruCache::<i32, i32>::new(0);
+    }
+
+    // Recency kept in a `Vec`, most recently used first.
+    struct Model {
+        entries: Vec<(i32, i32)>,
+        capacity: usize,
+        stats: CacheStats,
+        evicted: Vec<(i32, i32)>,
+    }
+
+    impl Model {
+        fn get(&mut self, key: i32) -> Option<i32> {
+            match self.entries.iter().position(|&(k, _)| k == key) {
+                Some(index) => {
+                    self.stats.hits += 1;
+                    let entry = self.entries.remove(index);
+                    self.entries.insert(0, entry);
+                    Some(entry.1)
+                }
+                None => {
+                    self.stats.misses += 1;
+                    None
+                }
+            }
+        }
+
+        fn put(&mut self, key: i32, value: i32) -> Option<i32> {
+            let old = match self.entries.iter().position(|&(k, _)| k == key) {
+                Some(index) => Some(self.entries.remove(index).1),
+                None => {
+                    if self.entries.len() == self.capacity {
+                        self.evicted.push(self.entries.pop().unwrap());
+                        self.stats.evictions += 1;
+                    }
+                    None
+                }
+            };
+            self.entries.insert(0, (key, value));
+            old
+        }
+
+        fn remove(&mut self, key: i32) -> Option<i32> {
+            let index = self.entries.iter().position(|&(k, _)| k == key)?;
+            Some(self.entries.remove(index).1)
+        }
+    }
+
+    #[test]
+    fn test_matches_model() {
+        let mut rng = Rng(0x5eed_1234);
+        for _ in 0..50 {
+            let capacity = rng.below(6) + 1;
+            let evicted = Rc::new(RefCell::new(Vec::new()));
+            let sink = evicted.clone();
+            let mut cache = LruCache::new(capacity).on_evict(move |key, value| {
+                sink.borrow_mut().push((key, value));
+            });
+            let mut model = Model {
+                entries: Vec::new(),
+                capacity,
+                stats: CacheStats::default(),
+                evicted: Vec::new(),
+            };
+
+            for step in 0..300 {
+                let key = rng.below(10) as i32;
+                match rng.below(4) {
+                    0 | 1 => assert_eq!(cache.get(&key).copied(), model.get(key)),
+                    2 => assert_eq!(cache.put(key, step), model.put(key, step)),
+                    _ => assert_eq!(cache.remove(&key), model.remove(key)),
+                }
+
+                let entries: Vec<(i32, i32)> = cache.iter().map(|(k, v)| (*k, *v)).collect();
+                assert_eq!(entries, model.entries);
+                assert_eq!(cache.len(), model.entries.len());
+            }
+
+            assert_eq!(cache.stats(), model.stats);
+            assert_eq!(*evicted.borrow(), model.evicted);
+        }
+    }
+}
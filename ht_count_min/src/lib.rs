@@ -0,0 +1,247 @@
+//! Frequency estimates for large streams in bounded memory.
+//!
+//! Sketches default to [`DefaultBuildHasher`], so sketches built apart hash
+//! alike and can be merged.
+
+use std::f64::consts::E;
+use std::hash::{BuildHasher, Hash};
+
+use ht_vanila::hashers::{DefaultBuildHasher, finalize, hash_key};
+
+/// A count-min sketch: `depth` rows of `width` counters. Every item adds to
+/// one counter per row, and its estimate is the smallest of those.
+///
+/// Estimates never fall below the true count. With the `epsilon` and
+/// `delta` given to [`new`](CountMinSketch::new), an estimate exceeds it by
+/// more than `epsilon * total()` with probability at most `delta`.
+#[derive(Clone)]
+pub struct CountMinSketch<S = DefaultBuildHasher> {
+    // row-major, `depth` rows of `width`
+    counters: Vec<u64>,
+    width: usize,
+    depth: usize,
+    total: u64,
+    hash_builder: S,
+}
+
+impl CountMinSketch<DefaultBuildHasher> {
+    /// # Panics
+    /// Panics unless `epsilon > 0` and `0 < delta < 1`.
+    pub fn new(epsilon: f64, delta: f64) -> Self {
+        Self::with_hasher(epsilon, delta, DefaultBuildHasher::default())
+    }
+}
+
+impl<S> CountMinSketch<S>
+where
+    S: BuildHasher,
+{
+    /// Sketches that will be merged must hash alike, so give them clones of
+    /// one `hash_builder`.
+    ///
+    /// # Panics
+    /// Panics unless `epsilon > 0` and `0 < delta < 1`.
+    pub fn with_hasher(epsilon: f64, delta: f64, hash_builder: S) -> Self {
+        assert!(epsilon > 0.0, "epsilon must be greater than zero");
+        assert!(delta > 0.0 && delta < 1.0, "delta must be between 0 and 1");
+        // w = e / epsilon and d = ln(1 / delta) give the bound above
+        let width = (E / epsilon).ceil() as usize;
+        let depth = ((1.0 / delta).ln().ceil() as usize).max(1);
+        CountMinSketch {
+            counters: vec![0; width * depth],
+            width,
+            depth,
+            total: 0,
+            hash_builder,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    /// Sum of every count added.
+    pub fn total(&self) -> u64 {
+        self.total
+    }
+
+    /// Counters and the total stop at `u64::MAX` instead of wrapping.
+    pub fn add<T>(&mut self, item: &T, count: u64)
+    where
+        T: Hash + ?Sized,
+    {
+        for row in 0..self.depth {
+            let cell = self.cell(row, item);
+            let counter = &mut self.counters[cell];
+            *counter = counter.saturating_add(count);
+        }
+        self.total = self.total.saturating_add(count);
+    }
+
+    /// At least the true count of `item`, and usually close to it.
+    pub fn estimate<T>(&self, item: &T) -> u64
+    where
+        T: Hash + ?Sized,
+    {
+        (0..self.depth)
+            .map(|row| self.counters[self.cell(row, item)])
+            .min()
+            .unwrap_or(0)
+    }
+
+    /// Adds the counts of `other`, as if its stream had been added here.
+    ///
+    /// # Panics
+    /// Panics if the two sketches have different dimensions.
+    pub fn merge(&mut self, other: &Self) {
+        assert!(
+            self.width == other.width && self.depth == other.depth,
+            "cannot merge sketches of different dimensions"
+        );
+        for (counter, &count) in self.counters.iter_mut().zip(&other.counters) {
+            *counter = counter.saturating_add(count);
+        }
+        self.total = self.total.saturating_add(other.total);
+    }
+
+    pub fn clear(&mut self) {
+        self.counters.fill(0);
+        self.total = 0;
+    }
+
+    // The counter of `item` in `row`. Each row hashes `(row, item)`, so two
+    // items that collide in one row are no more likely to collide in the
+    // others, and finalizes it because FNV's low bits alone spread poorly.
+    fn cell<T>(&self, row: usize, item: &T) -> usize
+    where
+        T: Hash + ?Sized,
+    {
+        let hash = finalize(hash_key(&self.hash_builder, &(row, item)));
+        row * self.width + (hash % self.width as u64) as usize
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // Key `k` appears `k % 50 + 1` times.
+    fn stream(keys: std::ops::Range<u32>) -> impl Iterator<Item = (u32, u64)> {
+        keys.map(|key| (key, u64::from(key % 50) + 1))
+    }
+
+    #[test]
+    fn test_dimensions() {
+        let sketch = CountMinSketch::new(0.01, 0.01);
+        assert_eq!(sketch.width(), 272);
+        assert_eq!(sketch.depth(), 5);
+    }
+
+    #[test]
+    fn test_single_item_is_exact() {
+        let mut sketch = CountMinSketch::new(0.01, 0.01);
+        sketch.add("a", 3);
+        sketch.add("a", 4);
+        assert_eq!(sketch.estimate("a"), 7);
+        assert_eq!(sketch.estimate("b"), 0);
+        assert_eq!(sketch.total(), 7);
+
+        sketch.clear();
+        assert_eq!(sketch.estimate("a"), 0);
+    }
+
+    #[test]
+    fn test_error_bound_holds() {
+        let (epsilon, delta) = (0.001, 0.01);
+        let mut sketch = CountMinSketch::new(epsilon, delta);
+        for (key, count) in stream(0..20_000) {
+            sketch.add(&key, count);
+        }
+
+        let allowed = epsilon * sketch.total() as f64;
+        let mut over = 0;
+        for (key, count) in stream(0..20_000) {
+            let estimate = sketch.estimate(&key);
+            assert!(estimate >= count, "underestimated {}", key);
+            if (estimate - count) as f64 > allowed {
+                over += 1;
+            }
+        }
+        assert!(over as f64 <= 20_000.0 * delta, "{} over the bound", over);
+    }
+
+    #[test]
+    fn test_merge_matches_one_sketch_of_both_streams() {
+        let mut left = CountMinSketch::new(0.01, 0.05);
+        let mut right = CountMinSketch::new(0.01, 0.05);
+        let mut both = CountMinSketch::new(0.01, 0.05);
+        for (key, count) in stream(0..3000) {
+            left.add(&key, count);
+            both.add(&key, count);
+        }
+        for (key, count) in stream(2000..5000) {
+            right.add(&key, count);
+            both.add(&key, count);
+        }
+
+        left.merge(&right);
+        assert_eq!(left.total(), both.total());
+        assert_eq!(left.counters, both.counters);
+        // key 2537 is in both streams, 38 times each
+        assert!(left.estimate(&2537) >= 76);
+    }
+
+    #[test]
+    fn test_rows_hash_independently() {
+        let sketch = CountMinSketch::new(0.01, 0.01);
+        // pairs sharing a counter in the first row rarely share another
+        let cells: Vec<(usize, usize)> = (0..2000u32)
+            .map(|key| (sketch.cell(0, &key), sketch.cell(1, &key)))
+            .collect();
+        let mut same_first = 0;
+        let mut same_second = 0;
+        for (i, a) in cells.iter().enumerate() {
+            for b in &cells[i + 1..] {
+                if a.0 == b.0 {
+                    same_first += 1;
+                    if a.1 == b.1 {
+                        same_second += 1;
+                    }
+                }
+            }
+        }
+        assert!(same_first > 0);
+        assert!(
+            (same_second as f64) < same_first as f64 * 5.0 / sketch.width() as f64,
+            "{} of {}",
+            same_second,
+            same_first
+        );
+    }
+
+    #[test]
+    fn test_counts_saturate() {
+        let mut sketch = CountMinSketch::new(0.1, 0.1);
+        sketch.add("a", u64::MAX);
+        sketch.add("a", 1);
+        assert_eq!(sketch.estimate("a"), u64::MAX);
+        assert_eq!(sketch.total(), u64::MAX);
+
+        let mut other = CountMinSketch::new(0.1, 0.1);
+        other.add("a", 5);
+        sketch.merge(&other);
+        assert_eq!(sketch.estimate("a"), u64::MAX);
+        assert_eq!(sketch.total(), u64::MAX);
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot merge sketches of different dimensions")]
+    fn test_merge_different_dimensions() {
+        let mut sketch = CountMinSketch::new(0.01, 0.01);
+        sketch.merge(&CountMinSketch::new(0.1, 0.01));
+    }
+}
@@ -0,0 +1,98 @@
+//! Spreading keys over a changing set of nodes, such as cache servers, so
+//! that adding or removing a node moves as few keys as possible.
+//!
+//! [`HashRing`] is consistent hashing with virtual nodes; [`Rendezvous`] is
+//! highest-random-weight hashing. Both hash through [`hash_key`] and default
+//! to [`DefaultBuildHasher`], so every process places keys the same way, and
+//! spread the hashes with [`finalize`] before comparing them.
+//!
+//! [`hash_key`]: ht_vanila::hashers::hash_key
+//! [`finalize`]: ht_vanila::hashers::finalize
+//! [`DefaultBuildHasher`]: ht_vanila::hashers::DefaultBuildHasher
+
+use std::hash::Hash;
+
+use ht_vanila::HashTable;
+
+mod rendezvous;
+mod ring;
+
+pub use rendezvous::Rendezvous;
+pub use ring::HashRing;
+
+/// Maps keys to nodes.
+pub trait Placement<N> {
+    /// The node owning `key`, or `None` when there are no nodes.
+    fn node_for<Q>(&self, key: &Q) -> Option<&N>
+    where
+        Q: Hash + ?Sized;
+}
+
+/// How many of a sample of keys changed node between two placements.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct MoveReport {
+    pub keys: usize,
+    pub moved: usize,
+}
+
+impl MoveReport {
+    /// Fraction of the keys that moved, or 0 for an empty sample.
+    pub fn moved_fraction(&self) -> f64 {
+        if self.keys == 0 {
+            return 0.0;
+        }
+        self.moved as f64 / self.keys as f64
+    }
+}
+
+/// Compares where `keys` land before and after a membership change.
+pub fn moved_keys<'a, N, P, Q>(
+    before: &P,
+    after: &P,
+    keys: impl IntoIterator<Item = &'a Q>,
+) -> MoveReport
+where
+    N: PartialEq,
+    P: Placement<N>,
+    Q: Hash + 'a,
+{
+    let mut report = MoveReport::default();
+    for key in keys {
+        report.keys += 1;
+        if before.node_for(key) != after.node_for(key) {
+            report.moved += 1;
+        }
+    }
+    report
+}
+
+/// How many of `keys` each node owns, for checking the balance.
+pub fn keys_per_node<'a, N, P, Q>(
+    placement: &P,
+    keys: impl IntoIterator<Item = &'a Q>,
+) -> HashTable<N, usize>
+where
+    N: Hash + PartialEq + Clone,
+    P: Placement<N>,
+    Q: Hash + 'a,
+{
+    let mut counts = HashTable::new(16);
+    for key in keys {
+        if let Some(node) = placement.node_for(key) {
+            *counts.entry(node.clone()).or_insert(0) += 1;
+        }
+    }
+    counts
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_moved_fraction() {
+        let report = MoveReport { keys: 8, moved: 2 };
+        assert_eq!(report.moved_fraction(), 0.25);
+        assert_eq!(MoveReport::default().moved_fraction(), 0.0);
+    }
+}
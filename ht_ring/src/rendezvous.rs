@@ -0,0 +1,172 @@
+use std::hash::{BuildHasher, Hash};
+
+use ht_vanila::HashSet;
+use ht_vanila::hashers::{DefaultBuildHasher, finalize, hash_key};
+
+use crate::Placement;
+
+/// Rendezvous (highest random weight) hashing: a key belongs to the node
+/// that scores highest on `hash(node, key)`.
+///
+/// Needs no ring and moves only the keys that must move, like
+/// [`HashRing`](crate::HashRing), but every lookup scores all nodes, so it
+/// costs O(nodes) instead of O(log(virtual nodes)).
+pub struct Rendezvous<N, S = DefaultBuildHasher>
+where
+    N: Hash + PartialEq,
+{
+    nodes: HashSet<N, S>,
+}
+
+impl<N> Rendezvous<N, DefaultBuildHasher>
+where
+    N: Hash + PartialEq,
+{
+    pub fn new() -> Self {
+        Self::with_hasher(DefaultBuildHasher::default())
+    }
+}
+
+impl<N> Default for Rendezvous<N, DefaultBuildHasher>
+where
+    N: Hash + PartialEq,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<N, S> Rendezvous<N, S>
+where
+    N: Hash + PartialEq,
+    S: BuildHasher,
+{
+    pub fn with_hasher(hash_builder: S) -> Self {
+        Rendezvous {
+            nodes: HashSet::with_capacity_and_hasher(8, hash_builder),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    pub fn contains_node(&self, node: &N) -> bool {
+        self.nodes.contains(node)
+    }
+
+    pub fn nodes(&self) -> impl Iterator<Item = &N> {
+        self.nodes.iter()
+    }
+
+    /// Returns whether `node` was new.
+    pub fn add_node(&mut self, node: N) -> bool {
+        self.nodes.insert(node)
+    }
+
+    /// Returns whether `node` was there.
+    pub fn remove_node(&mut self, node: &N) -> bool {
+        self.nodes.remove(node)
+    }
+}
+
+impl<N, S> Placement<N> for Rendezvous<N, S>
+where
+    N: Hash + PartialEq,
+    S: BuildHasher,
+{
+    fn node_for<Q>(&self, key: &Q) -> Option<&N>
+    where
+        Q: Hash + ?Sized,
+    {
+        self.nodes
+            .iter()
+            .max_by_key(|&node| finalize(hash_key(self.nodes.hasher(), &(node, key))))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{keys_per_node, moved_keys};
+
+    const KEYS: u32 = 20_000;
+
+    fn rendezvous(nodes: &[&'static str]) -> Rendezvous<&'static str> {
+        let mut rendezvous = Rendezvous::new();
+        for &node in nodes {
+            rendezvous.add_node(node);
+        }
+        rendezvous
+    }
+
+    fn keys() -> Vec<u32> {
+        (0..KEYS).collect()
+    }
+
+    #[test]
+    fn test_empty_and_membership() {
+        let mut rendezvous = rendezvous(&[]);
+        assert_eq!(rendezvous.node_for(&1), None);
+
+        assert!(rendezvous.add_node("a"));
+        assert!(!rendezvous.add_node("a"));
+        assert_eq!(rendezvous.node_for(&1), Some(&"a"));
+        assert!(rendezvous.remove_node(&"a"));
+        assert!(rendezvous.is_empty());
+    }
+
+    #[test]
+    fn test_placement_is_pinned() {
+        // every process and toolchain must agree on these, or a cluster
+        // sharing the placement would disagree on where keys live
+        let placement = rendezvous(&["a", "b", "c"]);
+        let owners: Vec<_> = ["user:1", "user:2", "user:3", "user:4", "user:5", "user:6"]
+            .iter()
+            .map(|key| *placement.node_for(*key).unwrap())
+            .collect();
+        assert_eq!(owners, ["c", "c", "c", "a", "b", "b"]);
+    }
+
+    #[test]
+    fn test_keys_are_balanced() {
+        let rendezvous = rendezvous(&["a", "b", "c", "d"]);
+        let counts = keys_per_node(&rendezvous, &keys());
+        for (node, &count) in &counts {
+            assert!(
+                (KEYS / 5..KEYS / 3).contains(&(count as u32)),
+                "{} has {}",
+                node,
+                count
+            );
+        }
+    }
+
+    #[test]
+    fn test_adding_a_node_moves_keys_only_to_it() {
+        let before = rendezvous(&["a", "b", "c", "d"]);
+        let after = rendezvous(&["a", "b", "c", "d", "e"]);
+
+        let fraction = moved_keys(&before, &after, &keys()).moved_fraction();
+        assert!((0.15..0.25).contains(&fraction), "{}", fraction);
+        for key in keys() {
+            if before.node_for(&key) != after.node_for(&key) {
+                assert_eq!(after.node_for(&key), Some(&"e"));
+            }
+        }
+    }
+
+    #[test]
+    fn test_removing_a_node_moves_only_its_keys() {
+        let before = rendezvous(&["a", "b", "c", "d"]);
+        let mut after = rendezvous(&["a", "b", "c", "d"]);
+        after.remove_node(&"c");
+
+        let owned_by_c = keys_per_node(&before, &keys())[&"c"];
+        assert_eq!(moved_keys(&before, &after, &keys()).moved, owned_by_c);
+    }
+}
@@ -0,0 +1,210 @@
+use std::hash::{BuildHasher, Hash};
+
+use ht_vanila::HashSet;
+use ht_vanila::hashers::{DefaultBuildHasher, finalize, hash_key};
+
+use crate::Placement;
+
+/// Consistent hashing: every node is hashed onto a ring of `u64` positions
+/// at several points (virtual nodes), and a key belongs to the first one at
+/// or after its own hash, wrapping around.
+///
+/// Adding a node only takes keys from its neighbours on the ring, so about
+/// `1 / nodes` of the keys move. More virtual nodes even out the share each
+/// node gets, at the cost of a larger ring to search.
+pub struct HashRing<N, S = DefaultBuildHasher>
+where
+    N: Hash + PartialEq,
+{
+    nodes: HashSet<N, S>,
+    // (position, node), sorted by position
+    ring: Vec<(u64, N)>,
+    virtual_nodes: usize,
+}
+
+impl<N> HashRing<N, DefaultBuildHasher>
+where
+    N: Hash + PartialEq + Clone,
+{
+    /// # Panics
+    /// Panics if `virtual_nodes` is 0.
+    pub fn new(virtual_nodes: usize) -> Self {
+        Self::with_hasher(virtual_nodes, DefaultBuildHasher::default())
+    }
+}
+
+impl<N, S> HashRing<N, S>
+where
+    N: Hash + PartialEq + Clone,
+    S: BuildHasher,
+{
+    /// # Panics
+    /// Panics if `virtual_nodes` is 0.
+    pub fn with_hasher(virtual_nodes: usize, hash_builder: S) -> Self {
+        assert!(
+            virtual_nodes > 0,
+            "virtual node count must be greater than zero"
+        );
+        HashRing {
+            nodes: HashSet::with_capacity_and_hasher(8, hash_builder),
+            ring: Vec::new(),
+            virtual_nodes,
+        }
+    }
+
+    /// Number of real nodes.
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    pub fn contains_node(&self, node: &N) -> bool {
+        self.nodes.contains(node)
+    }
+
+    pub fn nodes(&self) -> impl Iterator<Item = &N> {
+        self.nodes.iter()
+    }
+
+    /// Adds `node` with its virtual nodes, returning whether it was new.
+    pub fn add_node(&mut self, node: N) -> bool {
+        if self.nodes.contains(&node) {
+            return false;
+        }
+
+        for replica in 0..self.virtual_nodes {
+            let position = finalize(hash_key(self.nodes.hasher(), &(&node, replica)));
+            self.ring.push((position, node.clone()));
+        }
+        self.ring.sort_unstable_by_key(|&(position, _)| position);
+        self.nodes.insert(node);
+        true
+    }
+
+    /// Removes `node` with its virtual nodes, returning whether it was there.
+    pub fn remove_node(&mut self, node: &N) -> bool {
+        if !self.nodes.remove(node) {
+            return false;
+        }
+        self.ring.retain(|(_, n)| n != node);
+        true
+    }
+}
+
+impl<N, S> Placement<N> for HashRing<N, S>
+where
+    N: Hash + PartialEq + Clone,
+    S: BuildHasher,
+{
+    fn node_for<Q>(&self, key: &Q) -> Option<&N>
+    where
+        Q: Hash + ?Sized,
+    {
+        let hash = finalize(hash_key(self.nodes.hasher(), key));
+        let index = self.ring.partition_point(|&(position, _)| position < hash);
+        // past the last position wraps to the first
+        let (_, node) = self.ring.get(index).or_else(|| self.ring.first())?;
+        Some(node)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{keys_per_node, moved_keys};
+
+    const KEYS: u32 = 20_000;
+
+    fn ring(nodes: &[&'static str]) -> HashRing<&'static str> {
+        let mut ring = HashRing::new(160);
+        for &node in nodes {
+            ring.add_node(node);
+        }
+        ring
+    }
+
+    fn keys() -> Vec<u32> {
+        (0..KEYS).collect()
+    }
+
+    #[test]
+    fn test_empty_and_single_node() {
+        let mut ring = ring(&[]);
+        assert_eq!(ring.node_for(&1), None);
+
+        ring.add_node("a");
+        assert!(keys().iter().all(|key| ring.node_for(key) == Some(&"a")));
+        assert!(!ring.add_node("a"));
+        assert_eq!(ring.len(), 1);
+    }
+
+    #[test]
+    fn test_placement_ignores_insertion_order() {
+        let forward = ring(&["a", "b", "c"]);
+        let backward = ring(&["c", "b", "a"]);
+        assert_eq!(moved_keys(&forward, &backward, &keys()).moved, 0);
+    }
+
+    #[test]
+    fn test_placement_is_pinned() {
+        // every process and toolchain must agree on these, or a cluster
+        // sharing the placement would disagree on where keys live
+        let placement = ring(&["a", "b", "c"]);
+        let owners: Vec<_> = ["user:1", "user:2", "user:3", "user:4", "user:5", "user:6"]
+            .iter()
+            .map(|key| *placement.node_for(*key).unwrap())
+            .collect();
+        assert_eq!(owners, ["a", "c", "b", "b", "c", "c"]);
+    }
+
+    #[test]
+    fn test_keys_are_balanced() {
+        let ring = ring(&["a", "b", "c", "d"]);
+        let counts = keys_per_node(&ring, &keys());
+        for (node, &count) in &counts {
+            // a quarter each, give or take
+            assert!(
+                (KEYS / 5..KEYS / 3).contains(&(count as u32)),
+                "{} has {}",
+                node,
+                count
+            );
+        }
+    }
+
+    #[test]
+    fn test_adding_a_node_moves_keys_only_to_it() {
+        let before = ring(&["a", "b", "c", "d"]);
+        let after = ring(&["a", "b", "c", "d", "e"]);
+
+        let report = moved_keys(&before, &after, &keys());
+        let fraction = report.moved_fraction();
+        assert!((0.12..0.28).contains(&fraction), "{}", fraction);
+        for key in keys() {
+            if before.node_for(&key) != after.node_for(&key) {
+                assert_eq!(after.node_for(&key), Some(&"e"));
+            }
+        }
+    }
+
+    #[test]
+    fn test_removing_a_node_moves_only_its_keys() {
+        let before = ring(&["a", "b", "c", "d"]);
+        let mut after = ring(&["a", "b", "c", "d"]);
+        assert!(after.remove_node(&"b"));
+        assert!(!after.remove_node(&"b"));
+        assert!(!after.contains_node(&"b"));
+
+        let owned_by_b = keys_per_node(&before, &keys())[&"b"];
+        assert_eq!(moved_keys(&before, &after, &keys()).moved, owned_by_b);
+    }
+
+    #[test]
+    #[should_panic(expected = "virtual node count must be greater than zero")]
+    fn test_zero_virtual_nodes() {
+        HashRing::<u32>::new(0);
+    }
+}
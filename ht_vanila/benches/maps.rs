@@ -0,0 +1,141 @@
+//! Compares every `Map` implementation on insert-, lookup- and remove-heavy
+//! workloads, then how evenly each bundled hasher spreads the keys. Run with
+//! `cargo bench -p ht_vanila`; pass a key count to change the size, e.g.
+//! `cargo bench -p ht_vanila -- 100000`.
+
+use std::collections::HashMap;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use ht_vanila::hashers::{FnvBuildHasher, MultiplicativeBuildHasher};
+use ht_vanila::open_addressing::{CuckooTable, LinearProbingTable, RobinHoodTable};
+use ht_vanila::{HashTable, Map};
+
+const ROUNDS: u32 = 5;
+
+// Spreads sequential numbers so keys don't arrive in hash order.
+fn keys(n: usize) -> Vec<u64> {
+    (0..n as u64)
+        .map(|i| i.wrapping_mul(0x9e37_79b9_7f4a_7c15))
+        .collect()
+}
+
+fn insert_heavy<M: Map<u64, u64>>(map: &mut M, keys: &[u64]) {
+    for &key in keys {
+        map.insert(key, key);
+    }
+    // overwrite half of them
+    for &key in keys.iter().step_by(2) {
+        map.insert(key, key + 1);
+    }
+}
+
+fn lookup_heavy<M: Map<u64, u64>>(map: &mut M, keys: &[u64]) {
+    for &key in keys {
+        map.insert(key, key);
+    }
+    for _ in 0..10 {
+        for &key in keys {
+            black_box(map.get(&key));
+            // a miss for every hit
+            black_box(map.get(&!key));
+        }
+    }
+}
+
+fn remove_heavy<M: Map<u64, u64>>(map: &mut M, keys: &[u64]) {
+    for _ in 0..3 {
+        for &key in keys {
+            map.insert(key, key);
+        }
+        for &key in keys {
+            black_box(map.remove(&key));
+        }
+    }
+}
+
+// Best of `ROUNDS`, each on a fresh map.
+fn time<M, F>(make: &dyn Fn() -> M, workload: F, keys: &[u64]) -> Duration
+where
+    M: Map<u64, u64>,
+    F: Fn(&mut M, &[u64]),
+{
+    (0..ROUNDS)
+        .map(|_| {
+            let mut map = make();
+            let start = Instant::now();
+            workload(&mut map, keys);
+            let elapsed = start.elapsed();
+            black_box(map.len());
+            elapsed
+        })
+        .min()
+        .unwrap()
+}
+
+fn run<M: Map<u64, u64>>(name: &str, make: &dyn Fn() -> M, keys: &[u64]) {
+    let insert = time(make, insert_heavy, keys);
+    let lookup = time(make, lookup_heavy, keys);
+    let remove = time(make, remove_heavy, keys);
+    println!(
+        "{:<16} {:>12.2?} {:>12.2?} {:>12.2?}",
+        name, insert, lookup, remove
+    );
+}
+
+// How evenly each bundled hasher spreads the same keys over the buckets.
+fn distribution<S: std::hash::BuildHasher>(name: &str, hash_builder: S, keys: &[u64]) {
+    let mut table = HashTable::with_capacity_and_hasher(16, hash_builder);
+    for &key in keys {
+        table.insert(key, ());
+    }
+    let stats = table.stats();
+    println!(
+        "{:<16} {:>12} {:>12} {:>12.3}",
+        name, stats.longest_chain, stats.empty_buckets, stats.average_probes
+    );
+}
+
+fn main() {
+    // `cargo bench` passes `--bench`; any other argument is the key count
+    let n = std::env::args()
+        .skip(1)
+        .find_map(|arg| arg.parse().ok())
+        .unwrap_or(50_000);
+    let keys = keys(n);
+
+    println!("{n} keys, best of {ROUNDS} rounds");
+    println!(
+        "{:<16} {:>12} {:>12} {:>12}",
+        "map", "insert", "lookup", "remove"
+    );
+    run("chaining", &|| HashTable::new(16), &keys);
+    run(
+        "chaining incr",
+        &|| HashTable::new(16).incremental_rehash(true),
+        &keys,
+    );
+    run(
+        "chaining fnv",
+        &|| HashTable::with_capacity_and_hasher(16, FnvBuildHasher::default()),
+        &keys,
+    );
+    run(
+        "chaining mul",
+        &|| HashTable::with_capacity_and_hasher(16, MultiplicativeBuildHasher::default()),
+        &keys,
+    );
+    run("linear probing", &|| LinearProbingTable::new(16), &keys);
+    run("robin hood", &|| RobinHoodTable::new(16), &keys);
+    run("cuckoo", &|| CuckooTable::new(16), &keys);
+    run("std HashMap", &HashMap::new, &keys);
+
+    println!();
+    println!(
+        "{:<16} {:>12} {:>12} {:>12}",
+        "hasher", "longest", "empty", "probes"
+    );
+    distribution("sip", std::hash::RandomState::new(), &keys);
+    distribution("fnv", FnvBuildHasher::default(), &keys);
+    distribution("mul", MultiplicativeBuildHasher::default(), &keys);
+}
@@ -0,0 +1,204 @@
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hash};
+
+use crate::HashTable;
+
+/// A view into a single slot of a [`HashTable`], created by
+/// [`HashTable::entry`].
+pub enum Entry<'a, K, V, S = RandomState>
+where
+    K: Hash + PartialEq,
+{
+    Occupied(OccupiedEntry<'a, K, V, S>),
+    Vacant(VacantEntry<'a, K, V, S>),
+}
+
+pub struct OccupiedEntry<'a, K, V, S = RandomState>
+where
+    K: Hash + PartialEq,
+{
+    table: &'a mut HashTable<K, V, S>,
+    key: K,
+}
+
+pub struct VacantEntry<'a, K, V, S = RandomState>
+where
+    K: Hash + PartialEq,
+{
+    table: &'a mut HashTable<K, V, S>,
+    key: K,
+}
+
+impl<K, V, S> HashTable<K, V, S>
+where
+    K: Hash + PartialEq,
+    S: BuildHasher,
+{
+    pub fn entry(&mut self, key: K) -> Entry<'_, K, V, S> {
+        if self.contains_key(&key) {
+            Entry::Occupied(OccupiedEntry { table: self, key })
+        } else {
+            Entry::Vacant(VacantEntry { table: self, key })
+        }
+    }
+}
+
+impl<'a, K, V, S> Entry<'a, K, V, S>
+where
+    K: Hash + PartialEq,
+    S: BuildHasher,
+{
+    pub fn key(&self) -> &K {
+        match self {
+            Entry::Occupied(entry) => entry.key(),
+            Entry::Vacant(entry) => entry.key(),
+        }
+    }
+
+    pub fn or_insert(self, default: V) -> &'a mut V {
+        self.or_insert_with(|| default)
+    }
+
+    pub fn or_insert_with<F>(self, default: F) -> &'a mut V
+    where
+        F: FnOnce() -> V,
+    {
+        match self {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(default()),
+        }
+    }
+
+    pub fn or_default(self) -> &'a mut V
+    where
+        V: Default,
+    {
+        self.or_insert_with(V::default)
+    }
+
+    /// Runs `f` on the value if the entry is occupied.
+    pub fn and_modify<F>(mut self, f: F) -> Self
+    where
+        F: FnOnce(&mut V),
+    {
+        if let Entry::Occupied(entry) = &mut self {
+            f(entry.get_mut());
+        }
+        self
+    }
+}
+
+impl<'a, K, V, S> OccupiedEntry<'a, K, V, S>
+where
+    K: Hash + PartialEq,
+    S: BuildHasher,
+{
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+
+    pub fn get(&self) -> &V {
+        self.table.get(&self.key).expect("entry is occupied")
+    }
+
+    pub fn get_mut(&mut self) -> &mut V {
+        self.table.get_mut(&self.key).expect("entry is occupied")
+    }
+
+    pub fn into_mut(self) -> &'a mut V {
+        self.table.get_mut(&self.key).expect("entry is occupied")
+    }
+
+    /// Replaces the value and returns the old one.
+    pub fn insert(&mut self, value: V) -> V {
+        std::mem::replace(self.get_mut(), value)
+    }
+
+    pub fn remove(self) -> V {
+        self.table.remove(&self.key).expect("entry is occupied")
+    }
+}
+
+impl<'a, K, V, S> VacantEntry<'a, K, V, S>
+where
+    K: Hash + PartialEq,
+    S: BuildHasher,
+{
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+
+    pub fn into_key(self) -> K {
+        self.key
+    }
+
+    pub fn insert(self, value: V) -> &'a mut V {
+        let VacantEntry { table, key } = self;
+        table.push_new(key, value)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_entry_or_insert() {
+        let mut ht = HashTable::new(4);
+        *ht.entry("a").or_insert(0) += 1;
+        *ht.entry("a").or_insert(0) += 1;
+        *ht.entry("b").or_default() += 5;
+
+        assert_eq!(ht.get(&"a"), Some(&2));
+        assert_eq!(ht.get(&"b"), Some(&5));
+        assert_eq!(ht.len(), 2);
+    }
+
+    #[test]
+    fn test_entry_and_modify() {
+        let mut ht = HashTable::new(4);
+        ht.entry("a").and_modify(|v| *v += 1).or_insert(10);
+        ht.entry("a").and_modify(|v| *v += 1).or_insert(10);
+        assert_eq!(ht.get(&"a"), Some(&11));
+    }
+
+    #[test]
+    fn test_occupied_and_vacant() {
+        let mut ht = HashTable::new(4);
+        ht.insert("a", 1);
+
+        match ht.entry("a") {
+            Entry::Occupied(mut entry) => {
+                assert_eq!(entry.key(), &"a");
+                assert_eq!(entry.insert(2), 1);
+                assert_eq!(entry.get(), &2);
+                assert_eq!(entry.remove(), 2);
+            }
+            Entry::Vacant(_) => panic!("expected an occupied entry"),
+        }
+        assert!(ht.is_empty());
+
+        match ht.entry("b") {
+            Entry::Vacant(entry) => {
+                assert_eq!(entry.key(), &"b");
+                *entry.insert(3) += 1;
+            }
+            Entry::Occupied(_) => panic!("expected a vacant entry"),
+        }
+        assert_eq!(ht.get(&"b"), Some(&4));
+    }
+
+    #[test]
+    fn test_vacant_insert_resizes() {
+        let mut ht = HashTable::new(2);
+        for key in 0..20 {
+            ht.entry(key).or_insert_with(|| key * 10);
+        }
+
+        assert_eq!(ht.len(), 20);
+        assert!(ht.capacity > 20);
+        for key in 0..20 {
+            assert_eq!(ht.get(&key), Some(&(key * 10)));
+        }
+    }
+}
@@ -0,0 +1,261 @@
+//! Small non-cryptographic hashers to plug into [`HashTable`] through
+//! [`HashTable::with_hasher`]. They are fast but unseeded, so only use them
+//! for keys an attacker cannot choose.
+//!
+//! [`HashTable`]: crate::HashTable
+//! [`HashTable::with_hasher`]: crate::HashTable::with_hasher
+
+use std::hash::{BuildHasher, BuildHasherDefault, Hash, Hasher};
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// 64-bit FNV-1a: xor in each byte, then multiply by the FNV prime.
+///
+/// Integers are fed in as little-endian bytes, and `usize` as a `u64`, so a
+/// key hashes the same on every platform.
+#[derive(Debug, Clone, Copy)]
+pub struct FnvHasher(u64);
+
+impl Default for FnvHasher {
+    fn default() -> Self {
+        FnvHasher(FNV_OFFSET_BASIS)
+    }
+}
+
+impl Hasher for FnvHasher {
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 ^= byte as u64;
+            self.0 = self.0.wrapping_mul(FNV_PRIME);
+        }
+    }
+
+    fn write_u16(&mut self, i: u16) {
+        self.write(&i.to_le_bytes());
+    }
+
+    fn write_u32(&mut self, i: u32) {
+        self.write(&i.to_le_bytes());
+    }
+
+    fn write_u64(&mut self, i: u64) {
+        self.write(&i.to_le_bytes());
+    }
+
+    fn write_u128(&mut self, i: u128) {
+        self.write(&i.to_le_bytes());
+    }
+
+    fn write_usize(&mut self, i: usize) {
+        self.write_u64(i as u64);
+    }
+
+    fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
+pub type FnvBuildHasher = BuildHasherDefault<FnvHasher>;
+
+// 2^64 divided by the golden ratio.
+const GOLDEN_RATIO: u64 = 0x9e37_79b9_7f4a_7c15;
+
+/// Multiplicative (Fibonacci) hashing over 8-byte words. Very cheap for
+/// integer keys.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct MultiplicativeHasher(u64);
+
+impl MultiplicativeHasher {
+    fn mix(&mut self, word: u64) {
+        self.0 = (self.0.rotate_left(5) ^ word).wrapping_mul(GOLDEN_RATIO);
+    }
+}
+
+impl Hasher for MultiplicativeHasher {
+    fn write(&mut self, bytes: &[u8]) {
+        let mut chunks = bytes.chunks_exact(8);
+        for chunk in &mut chunks {
+            self.mix(u64::from_le_bytes(chunk.try_into().expect("8-byte chunk")));
+        }
+
+        let rest = chunks.remainder();
+        if !rest.is_empty() {
+            let mut word = [0; 8];
+            word[..rest.len()].copy_from_slice(rest);
+            self.mix(u64::from_le_bytes(word));
+        }
+    }
+
+    fn write_u64(&mut self, i: u64) {
+        self.mix(i);
+    }
+
+    fn write_usize(&mut self, i: usize) {
+        self.mix(i as u64);
+    }
+
+    fn write_u32(&mut self, i: u32) {
+        self.mix(i as u64);
+    }
+
+    fn finish(&self) -> u64 {
+        // the product's entropy sits in the high bits, but bucket indexes
+        // come from the low ones
+        self.0 ^ (self.0 >> 32)
+    }
+}
+
+pub type MultiplicativeBuildHasher = BuildHasherDefault<MultiplicativeHasher>;
+
+/// The default of structures that must hash alike in every process, such
+/// as placements shared by a cluster or sketches merged across machines.
+///
+/// It is [`FnvHasher`], whose output is fixed by the published FNV
+/// definition. std's `DefaultHasher` is not: its algorithm may change
+/// between Rust releases, and the default of [`HashTable`](crate::HashTable)
+/// is also randomly seeded.
+pub type DefaultBuildHasher = FnvBuildHasher;
+
+/// Hashes `value` with `hash_builder`. [`HashTable::hash`] is this hash, and
+/// the structures built on the table go through it too so they agree with
+/// the table on every key.
+///
+/// [`HashTable::hash`]: crate::HashTable::hash
+pub fn hash_key<T, S>(hash_builder: &S, value: &T) -> u64
+where
+    T: Hash + ?Sized,
+    S: BuildHasher,
+{
+    hash_builder.hash_one(value)
+}
+
+/// MurmurHash3's 64-bit finalizer: makes every input bit affect every
+/// output bit.
+///
+/// FNV carries a change in one byte only into the bits above it, which is
+/// enough for bucket indexes but not for structures that order whole
+/// hashes, like the positions of a consistent hashing ring. They finalize
+/// the hash first.
+pub fn finalize(hash: u64) -> u64 {
+    let mut hash = hash;
+    hash ^= hash >> 33;
+    hash = hash.wrapping_mul(0xff51_afd7_ed55_8ccd);
+    hash ^= hash >> 33;
+    hash = hash.wrapping_mul(0xc4ce_b9fe_1a85_ec53);
+    hash ^ (hash >> 33)
+}
+
+/// Derives `count` indexes below `len` from one 64-bit hash, the i-th being
+/// `h1 + i * h2` (Kirsch-Mitzenmacher double hashing). Probabilistic
+/// structures use it to get their k hash functions from a single
+/// [`HashTable::hash`](crate::HashTable::hash)-style hash.
+///
+/// # Panics
+/// Panics if `len` is 0.
+pub fn double_hash(hash: u64, count: usize, len: usize) -> impl Iterator<Item = usize> {
+    assert!(len > 0, "len must be greater than zero");
+    let h1 = hash;
+    // odd, so the sequence does not get stuck when `len` is a power of two
+    let h2 = hash.rotate_left(32) | 1;
+    (0..count as u64).map(move |i| (h1.wrapping_add(i.wrapping_mul(h2)) % len as u64) as usize)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::HashTable;
+    use std::hash::BuildHasher;
+
+    fn fnv(bytes: &[u8]) -> u64 {
+        let mut hasher = FnvHasher::default();
+        hasher.write(bytes);
+        hasher.finish()
+    }
+
+    #[test]
+    fn test_fnv_reference_values() {
+        // published FNV-1a 64-bit test vectors
+        assert_eq!(fnv(b""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(fnv(b"a"), 0xaf63_dc4c_8601_ec8c);
+        assert_eq!(fnv(b"foobar"), 0x8594_4171_f739_67e8);
+    }
+
+    #[test]
+    fn test_fnv_integers_are_little_endian() {
+        let fnv_build = FnvBuildHasher::default();
+        assert_eq!(fnv_build.hash_one(0x6261u16), fnv(b"ab"));
+        assert_eq!(fnv_build.hash_one(7u64), fnv(&7u64.to_le_bytes()));
+        assert_eq!(fnv_build.hash_one(7usize), fnv_build.hash_one(7u64));
+    }
+
+    #[test]
+    fn test_default_build_hasher_is_pinned() {
+        // changing this value moves every key of every stored placement
+        let hash_builder = DefaultBuildHasher::default();
+        assert_eq!(hash_key(&hash_builder, "foobar"), 0x3452_4ba7_168a_2c15);
+        assert_eq!(hash_key(&hash_builder, "foobar"), fnv(b"foobar\xff"));
+    }
+
+    #[test]
+    fn test_hashers_are_deterministic() {
+        let fnv = FnvBuildHasher::default();
+        assert_eq!(fnv.hash_one("key"), fnv.hash_one("key"));
+        assert_ne!(fnv.hash_one("key"), fnv.hash_one("kez"));
+
+        let mul = MultiplicativeBuildHasher::default();
+        assert_eq!(mul.hash_one(42u64), mul.hash_one(42u64));
+        assert_ne!(mul.hash_one(42u64), mul.hash_one(43u64));
+        assert_ne!(mul.hash_one("abcdefgh1"), mul.hash_one("abcdefgh2"));
+    }
+
+    #[test]
+    fn test_multiplicative_spreads_sequential_keys() {
+        let mul = MultiplicativeBuildHasher::default();
+        let mut buckets = [0; 16];
+        for key in 0..1600u64 {
+            buckets[mul.hash_one(key) as usize % 16] += 1;
+        }
+        assert!(buckets.iter().all(|&count| count > 50), "{:?}", buckets);
+    }
+
+    #[test]
+    fn test_finalize() {
+        assert_eq!(finalize(0), 0);
+        assert_eq!(finalize(1), 0xb456_bcfc_34c2_cb2c);
+        // neighbouring inputs land far apart
+        assert!((finalize(2) ^ finalize(3)).count_ones() > 16);
+    }
+
+    #[test]
+    fn test_double_hash() {
+        let indexes: Vec<usize> = double_hash(0x1234_5678_9abc_def0, 7, 64).collect();
+        assert_eq!(indexes.len(), 7);
+        assert!(indexes.iter().all(|&i| i < 64));
+        // an odd step over a power-of-two length never repeats early
+        let mut unique = indexes.clone();
+        unique.sort();
+        unique.dedup();
+        assert_eq!(unique.len(), 7);
+
+        assert_eq!(double_hash(42, 0, 10).count(), 0);
+    }
+
+    #[test]
+    fn test_table_with_custom_hasher() {
+        let mut ht = HashTable::with_hasher(FnvBuildHasher::default());
+        for key in 0..100 {
+            ht.insert(key, key * 2);
+        }
+        assert_eq!(ht.get(&42), Some(&84));
+
+        let mut ht = HashTable::with_capacity_and_hasher(4, MultiplicativeBuildHasher::default());
+        ht.insert("a", 1);
+        assert_eq!(ht[&"a"], 1);
+
+        // same keys hash the same across tables with the same hasher
+        let a: HashTable<&str, i32, FnvBuildHasher> = HashTable::default();
+        let b: HashTable<&str, i32, FnvBuildHasher> = HashTable::default();
+        assert_eq!(a.hash(&"key"), b.hash(&"key"));
+    }
+}
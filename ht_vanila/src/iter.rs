@@ -0,0 +1,461 @@
+use std::collections::{LinkedList, linked_list};
+use std::fmt;
+use std::hash::{BuildHasher, Hash};
+use std::iter::{Chain, FusedIterator};
+use std::{mem, slice, vec};
+
+use crate::{DEFAULT_CAPACITY, HashTable};
+
+// While an incremental rehash runs, entries sit in both bucket arrays.
+type Buckets<I> = Chain<I, I>;
+
+/// Borrowing iterator over `(&K, &V)`, created by [`HashTable::iter`].
+pub struct Iter<'a, K, V> {
+    buckets: Buckets<slice::Iter<'a, LinkedList<(K, V)>>>,
+    current: linked_list::Iter<'a, (K, V)>,
+    remaining: usize,
+}
+
+/// Iterator over `(&K, &mut V)`, created by [`HashTable::iter_mut`].
+pub struct IterMut<'a, K, V> {
+    buckets: Buckets<slice::IterMut<'a, LinkedList<(K, V)>>>,
+    current: linked_list::IterMut<'a, (K, V)>,
+    remaining: usize,
+}
+
+/// Owning iterator, created by [`HashTable::into_iter`].
+pub struct IntoIter<K, V> {
+    buckets: Buckets<vec::IntoIter<LinkedList<(K, V)>>>,
+    current: linked_list::IntoIter<(K, V)>,
+    remaining: usize,
+}
+
+pub struct Keys<'a, K, V> {
+    inner: Iter<'a, K, V>,
+}
+
+pub struct Values<'a, K, V> {
+    inner: Iter<'a, K, V>,
+}
+
+pub struct ValuesMut<'a, K, V> {
+    inner: IterMut<'a, K, V>,
+}
+
+/// Draining iterator, created by [`HashTable::drain`]. Entries that are not
+/// consumed are still removed when it is dropped.
+pub struct Drain<'a, K, V> {
+    buckets: Buckets<slice::IterMut<'a, LinkedList<(K, V)>>>,
+    current: linked_list::IntoIter<(K, V)>,
+    remaining: usize,
+}
+
+// All iterators yield entries in bucket order, which changes when the
+// table resizes.
+impl<K, V, S> HashTable<K, V, S>
+where
+    K: Hash + PartialEq,
+{
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        Iter {
+            buckets: self.old_buckets.iter().chain(self.buckets.iter()),
+            current: linked_list::Iter::default(),
+            remaining: self.size,
+        }
+    }
+
+    pub fn iter_mut(&mut self) -> IterMut<'_, K, V> {
+        IterMut {
+            buckets: self.old_buckets.iter_mut().chain(self.buckets.iter_mut()),
+            current: linked_list::IterMut::default(),
+            remaining: self.size,
+        }
+    }
+
+    pub fn keys(&self) -> Keys<'_, K, V> {
+        Keys { inner: self.iter() }
+    }
+
+    pub fn values(&self) -> Values<'_, K, V> {
+        Values { inner: self.iter() }
+    }
+
+    pub fn values_mut(&mut self) -> ValuesMut<'_, K, V> {
+        ValuesMut {
+            inner: self.iter_mut(),
+        }
+    }
+
+    /// Removes every entry, yielding them in bucket order. The buckets stay
+    /// allocated.
+    pub fn drain(&mut self) -> Drain<'_, K, V> {
+        let remaining = mem::replace(&mut self.size, 0);
+        Drain {
+            buckets: self.old_buckets.iter_mut().chain(self.buckets.iter_mut()),
+            current: linked_list::IntoIter::default(),
+            remaining,
+        }
+    }
+}
+
+impl<'a, K, V> Iterator for Iter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<(&'a K, &'a V)> {
+        loop {
+            if let Some((k, v)) = self.current.next() {
+                self.remaining -= 1;
+                return Some((k, v));
+            }
+            self.current = self.buckets.next()?.iter();
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<K, V> ExactSizeIterator for Iter<'_, K, V> {}
+
+impl<K, V> FusedIterator for Iter<'_, K, V> {}
+
+impl<K, V> Clone for Iter<'_, K, V> {
+    fn clone(&self) -> Self {
+        Iter {
+            buckets: self.buckets.clone(),
+            current: self.current.clone(),
+            remaining: self.remaining,
+        }
+    }
+}
+
+impl<'a, K, V> Iterator for IterMut<'a, K, V> {
+    type Item = (&'a K, &'a mut V);
+
+    fn next(&mut self) -> Option<(&'a K, &'a mut V)> {
+        loop {
+            if let Some((k, v)) = self.current.next() {
+                self.remaining -= 1;
+                return Some((&*k, v));
+            }
+            self.current = self.buckets.next()?.iter_mut();
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<K, V> ExactSizeIterator for IterMut<'_, K, V> {}
+
+impl<K, V> FusedIterator for IterMut<'_, K, V> {}
+
+impl<K, V> Iterator for IntoIter<K, V> {
+    type Item = (K, V);
+
+    fn next(&mut self) -> Option<(K, V)> {
+        loop {
+            if let Some(entry) = self.current.next() {
+                self.remaining -= 1;
+                return Some(entry);
+            }
+            self.current = self.buckets.next()?.into_iter();
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<K, V> ExactSizeIterator for IntoIter<K, V> {}
+
+impl<K, V> FusedIterator for IntoIter<K, V> {}
+
+impl<'a, K, V> Iterator for Keys<'a, K, V> {
+    type Item = &'a K;
+
+    fn next(&mut self) -> Option<&'a K> {
+        self.inner.next().map(|(k, _)| k)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<K, V> ExactSizeIterator for Keys<'_, K, V> {}
+
+impl<'a, K, V> Iterator for Values<'a, K, V> {
+    type Item = &'a V;
+
+    fn next(&mut self) -> Option<&'a V> {
+        self.inner.next().map(|(_, v)| v)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<K, V> ExactSizeIterator for Values<'_, K, V> {}
+
+impl<'a, K, V> Iterator for ValuesMut<'a, K, V> {
+    type Item = &'a mut V;
+
+    fn next(&mut self) -> Option<&'a mut V> {
+        self.inner.next().map(|(_, v)| v)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<K, V> ExactSizeIterator for ValuesMut<'_, K, V> {}
+
+impl<K, V, S> IntoIterator for HashTable<K, V, S>
+where
+    K: Hash + PartialEq,
+{
+    type Item = (K, V);
+    type IntoIter = IntoIter<K, V>;
+
+    fn into_iter(self) -> IntoIter<K, V> {
+        IntoIter {
+            buckets: self.old_buckets.into_iter().chain(self.buckets),
+            current: linked_list::IntoIter::default(),
+            remaining: self.size,
+        }
+    }
+}
+
+impl<'a, K, V, S> IntoIterator for &'a HashTable<K, V, S>
+where
+    K: Hash + PartialEq,
+{
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    fn into_iter(self) -> Iter<'a, K, V> {
+        self.iter()
+    }
+}
+
+impl<'a, K, V, S> IntoIterator for &'a mut HashTable<K, V, S>
+where
+    K: Hash + PartialEq,
+{
+    type Item = (&'a K, &'a mut V);
+    type IntoIter = IterMut<'a, K, V>;
+
+    fn into_iter(self) -> IterMut<'a, K, V> {
+        self.iter_mut()
+    }
+}
+
+impl<K, V, S> FromIterator<(K, V)> for HashTable<K, V, S>
+where
+    K: Hash + PartialEq,
+    S: BuildHasher + Default,
+{
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let iter = iter.into_iter();
+        let capacity = iter.size_hint().0.max(DEFAULT_CAPACITY);
+        let mut table = HashTable::with_capacity_and_hasher(capacity, S::default());
+        table.extend(iter);
+        table
+    }
+}
+
+impl<K, V, S> Extend<(K, V)> for HashTable<K, V, S>
+where
+    K: Hash + PartialEq,
+    S: BuildHasher,
+{
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        for (k, v) in iter {
+            self.insert(k, v);
+        }
+    }
+}
+
+impl<'a, K, V, S> Extend<(&'a K, &'a V)> for HashTable<K, V, S>
+where
+    K: Hash + PartialEq + Copy + 'a,
+    V: Copy + 'a,
+    S: BuildHasher,
+{
+    fn extend<I: IntoIterator<Item = (&'a K, &'a V)>>(&mut self, iter: I) {
+        self.extend(iter.into_iter().map(|(&k, &v)| (k, v)));
+    }
+}
+
+impl<K, V, S> fmt::Debug for HashTable<K, V, S>
+where
+    K: Hash + PartialEq + fmt::Debug,
+    V: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<K, V> Iterator for Drain<'_, K, V> {
+    type Item = (K, V);
+
+    fn next(&mut self) -> Option<(K, V)> {
+        loop {
+            if let Some(entry) = self.current.next() {
+                self.remaining -= 1;
+                return Some(entry);
+            }
+            self.current = mem::take(self.buckets.next()?).into_iter();
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<K, V> ExactSizeIterator for Drain<'_, K, V> {}
+
+impl<K, V> Drop for Drain<'_, K, V> {
+    fn drop(&mut self) {
+        self.for_each(drop);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn sample() -> HashTable<i32, i32> {
+        (0..10).map(|k| (k, k * 2)).collect()
+    }
+
+    fn sorted<T: Ord>(mut values: Vec<T>) -> Vec<T> {
+        values.sort();
+        values
+    }
+
+    #[test]
+    fn test_iter() {
+        let ht = sample();
+        let iter = ht.iter();
+        assert_eq!(iter.len(), 10);
+
+        let entries = sorted(iter.map(|(&k, &v)| (k, v)).collect());
+        assert_eq!(entries, (0..10).map(|k| (k, k * 2)).collect::<Vec<_>>());
+        assert_eq!(HashTable::<i32, i32>::new(4).iter().next(), None);
+    }
+
+    #[test]
+    fn test_keys_and_values() {
+        let ht = sample();
+        assert_eq!(
+            sorted(ht.keys().copied().collect()),
+            (0..10).collect::<Vec<_>>()
+        );
+        assert_eq!(ht.values().sum::<i32>(), 90);
+        assert_eq!(ht.keys().len(), 10);
+    }
+
+    #[test]
+    fn test_values_mut_and_iter_mut() {
+        let mut ht = sample();
+        for v in ht.values_mut() {
+            *v += 1;
+        }
+        for (k, v) in &mut ht {
+            *v -= k;
+        }
+        assert!(ht.values().all(|&v| v >= 1));
+        assert_eq!(ht[&9], 10);
+    }
+
+    #[test]
+    fn test_into_iter() {
+        let ht = sample();
+        let iter = ht.into_iter();
+        assert_eq!(iter.len(), 10);
+        assert_eq!(
+            sorted(iter.collect()),
+            (0..10).map(|k| (k, k * 2)).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_for_loop_by_reference() {
+        let ht = sample();
+        let mut sum = 0;
+        for (k, v) in &ht {
+            sum += k + v;
+        }
+        assert_eq!(sum, 135);
+    }
+
+    #[test]
+    fn test_from_iter_and_extend() {
+        let mut ht: HashTable<&str, i32> = [("a", 1), ("b", 2), ("a", 3)].into_iter().collect();
+        assert_eq!(ht.len(), 2);
+        assert_eq!(ht[&"a"], 3);
+
+        ht.extend(vec![("c", 4)]);
+        ht.extend([(&"d", &5)]);
+        assert_eq!(ht.len(), 4);
+        assert_eq!(ht[&"d"], 5);
+
+        // round trip through a std map
+        let std: std::collections::HashMap<&str, i32> = ht.clone().into_iter().collect();
+        let back: HashTable<&str, i32> = std.into_iter().collect();
+        assert_eq!(
+            sorted(back.into_iter().collect()),
+            sorted(ht.into_iter().collect())
+        );
+    }
+
+    #[test]
+    fn test_debug_is_map_like() {
+        let mut ht = HashTable::new(4);
+        assert_eq!(format!("{:?}", ht), "{}");
+
+        ht.insert("a", 1);
+        assert_eq!(format!("{:?}", ht), r#"{"a": 1}"#);
+    }
+
+    #[test]
+    fn test_drain() {
+        let mut ht = HashTable::new(4);
+        for key in 0..10 {
+            ht.insert(key, key * 2);
+        }
+
+        let drain = ht.drain();
+        assert_eq!(drain.len(), 10);
+        let mut drained: Vec<(i32, i32)> = drain.collect();
+        drained.sort();
+
+        assert_eq!(drained, (0..10).map(|k| (k, k * 2)).collect::<Vec<_>>());
+        assert!(ht.is_empty());
+        assert_eq!(ht.get(&3), None);
+    }
+
+    #[test]
+    fn test_drain_dropped_early_still_empties() {
+        let mut ht = HashTable::new(4);
+        for key in 0..10 {
+            ht.insert(key, key);
+        }
+
+        ht.drain().next();
+        assert!(ht.is_empty());
+        assert!(ht.buckets.iter().all(|el| el.is_empty()));
+
+        ht.insert(1, 1);
+        assert_eq!(ht.len(), 1);
+    }
+}
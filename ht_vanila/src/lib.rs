@@ -1,32 +1,139 @@
-#![allow(dead_code)]
-
 use std::collections::LinkedList;
-use std::collections::hash_map::DefaultHasher;
-use std::hash::{Hash, Hasher};
-
-#[derive(Clone, Debug)]
-struct HashTable<K, V>
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hash};
+use std::mem;
+use std::ops::Index;
+
+mod entry;
+pub mod hashers;
+mod iter;
+mod map;
+pub mod multimap;
+pub mod open_addressing;
+pub mod set;
+mod sharded;
+mod stats;
+
+pub use entry::{Entry, OccupiedEntry, VacantEntry};
+pub use iter::{Drain, IntoIter, Iter, IterMut, Keys, Values, ValuesMut};
+pub use map::Map;
+pub use multimap::MultiMap;
+pub use set::HashSet;
+pub use sharded::ShardedHashTable;
+pub use stats::TableStats;
+
+// Starting bucket count when collecting from an iterator.
+const DEFAULT_CAPACITY: usize = 16;
+
+const DEFAULT_GROW_LOAD_FACTOR: f64 = 0.75;
+const DEFAULT_SHRINK_LOAD_FACTOR: f64 = 0.25;
+
+// Old buckets moved per insert or remove while an incremental rehash runs.
+// Anything above 2 finishes a doubling before the next one is due.
+const MIGRATE_STEP: usize = 4;
+
+/// Separate-chaining hash table. Keys are hashed with `S`, which defaults to
+/// the randomly seeded SipHash of `std`.
+///
+/// The table doubles when an insert would push the load (entries per bucket)
+/// past the grow factor and halves when a remove drops it under the shrink
+/// factor, never below the capacity it was created with. With
+/// [`incremental_rehash`](HashTable::incremental_rehash) on, a resize only
+/// allocates the new buckets; the entries move over a few buckets per insert
+/// or remove.
+#[derive(Clone)]
+pub struct HashTable<K, V, S = RandomState>
 where
     K: Hash + PartialEq,
 {
     buckets: Vec<LinkedList<(K, V)>>,
     size: usize,
     capacity: usize,
+    hash_builder: S,
+    // buckets of the previous size while an incremental rehash runs, empty
+    // otherwise; those before `migrated` have been moved to `buckets`
+    old_buckets: Vec<LinkedList<(K, V)>>,
+    migrated: usize,
+    min_capacity: usize,
+    grow_load_factor: f64,
+    shrink_load_factor: f64,
+    incremental: bool,
+    resizes: usize,
 }
 
 // Constructor
-impl<K, V> HashTable<K, V>
+impl<K, V> HashTable<K, V, RandomState>
 where
     K: Hash + PartialEq,
 {
     pub fn new(capacity: usize) -> Self {
+        Self::with_capacity_and_hasher(capacity, RandomState::new())
+    }
+}
+
+impl<K, V, S> HashTable<K, V, S>
+where
+    K: Hash + PartialEq,
+{
+    pub fn with_hasher(hash_builder: S) -> Self {
+        Self::with_capacity_and_hasher(DEFAULT_CAPACITY, hash_builder)
+    }
+
+    /// A `capacity` of 0 is rounded up to one bucket.
+    pub fn with_capacity_and_hasher(capacity: usize, hash_builder: S) -> Self {
+        let capacity = capacity.max(1);
         HashTable {
             buckets: Self::new_buckets(capacity),
             size: 0,
             capacity,
+            hash_builder,
+            old_buckets: Vec::new(),
+            migrated: 0,
+            min_capacity: capacity,
+            grow_load_factor: DEFAULT_GROW_LOAD_FACTOR,
+            shrink_load_factor: DEFAULT_SHRINK_LOAD_FACTOR,
+            incremental: false,
+            resizes: 0,
         }
     }
 
+    /// Sets the loads that trigger growing and shrinking. A `shrink` of 0
+    /// turns shrinking off.
+    ///
+    /// # Panics
+    /// Panics unless `grow > 0` and `0 <= shrink < grow / 2`, so halving the
+    /// table never lands it above the grow factor.
+    pub fn load_factors(mut self, grow: f64, shrink: f64) -> Self {
+        assert!(
+            grow > 0.0 && shrink >= 0.0 && shrink * 2.0 < grow,
+            "load factors must satisfy 0 < grow and 0 <= shrink < grow / 2"
+        );
+        self.grow_load_factor = grow;
+        self.shrink_load_factor = shrink;
+        self
+    }
+
+    /// Spreads the work of each resize over the following inserts and
+    /// removes instead of rehashing every entry at once.
+    pub fn incremental_rehash(mut self, enabled: bool) -> Self {
+        self.incremental = enabled;
+        self
+    }
+
+    pub fn hasher(&self) -> &S {
+        &self.hash_builder
+    }
+
+    /// Number of buckets.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Whether an incremental rehash still has entries to move.
+    pub fn is_rehashing(&self) -> bool {
+        !self.old_buckets.is_empty()
+    }
+
     fn new_buckets(capacity: usize) -> Vec<LinkedList<(K, V)>> {
         let mut buckets = Vec::with_capacity(capacity);
         for _ in 0..capacity {
@@ -34,43 +141,57 @@ where
         }
         buckets
     }
+
+    // Fewest buckets that hold `len` entries within the grow factor.
+    fn buckets_for(&self, len: usize) -> usize {
+        ((len as f64 / self.grow_load_factor).ceil() as usize).max(1)
+    }
 }
 
 // Implementation
-impl<K, V> HashTable<K, V>
+impl<K, V, S> HashTable<K, V, S>
 where
     K: Hash + PartialEq,
+    S: BuildHasher,
 {
-    pub fn insert(&mut self, key: K, value: V) {
-        self.resize();
-        if Self::buckets_insert(&mut self.buckets, self.capacity, key, value) {
-            self.size += 1;
-        }
+    pub fn len(&self) -> usize {
+        self.size
     }
 
-    fn buckets_insert(
-        buckets: &mut Vec<LinkedList<(K, V)>>,
-        capacity: usize,
-        key: K,
-        value: V,
-    ) -> bool {
-        let index = Self::hash_index(&key, capacity);
-        let el = buckets.get_mut(index).unwrap();
+    pub fn is_empty(&self) -> bool {
+        self.size == 0
+    }
 
-        for (k, v) in el.iter_mut() {
-            if *k == key {
-                *v = value;
-                return false;
-            }
+    /// Inserts `key`, returning the value it replaced if it was present.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        if let Some(v) = self.get_mut(&key) {
+            let old = mem::replace(v, value);
+            self.migrate(MIGRATE_STEP);
+            // adds nothing, but may start a grow that a rehash held back
+            self.grow(0);
+            return Some(old);
         }
+        self.push_new(key, value);
+        None
+    }
 
+    // Adds an entry for a key that is not in the table, growing first if the
+    // new entry pushes the load past the grow factor.
+    pub(crate) fn push_new(&mut self, key: K, value: V) -> &mut V {
+        self.migrate(MIGRATE_STEP);
+        self.grow(1);
+        self.size += 1;
+        let el = self.bucket_mut(&key);
         el.push_back((key, value));
-        true
+        &mut el.back_mut().expect("just pushed").1
+    }
+
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.get(key).is_some()
     }
 
     pub fn get(&self, key: &K) -> Option<&V> {
-        let index = Self::hash_index(&key, self.capacity);
-        let el = self.buckets.get(index).unwrap();
+        let el = self.bucket(key);
 
         for (k, v) in el.iter() {
             if *k == *key {
@@ -81,61 +202,215 @@ where
         None
     }
 
+    pub fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+        let el = self.bucket_mut(key);
+
+        for (k, v) in el.iter_mut() {
+            if *k == *key {
+                return Some(v);
+            }
+        }
+
+        None
+    }
+
     pub fn remove(&mut self, key: &K) -> Option<V> {
-        let index = Self::hash_index(&key, self.capacity);
-        let el = self.buckets.get_mut(index).unwrap();
+        self.migrate(MIGRATE_STEP);
+        let el = self.bucket_mut(key);
         let mut extract = el
             .extract_if(|(k, _)| *k == *key)
             .collect::<LinkedList<_>>();
 
-        extract.pop_front().map(|(_, v)| {
+        let removed = extract.pop_front();
+        if removed.is_some() {
             self.size -= 1;
-            v
-        })
+        }
+        self.grow(0);
+        self.shrink();
+        removed.map(|(_, v)| v)
     }
 
-    fn resize(&mut self) {
-        let threshold = (self.capacity as f64) * 0.75;
-        if threshold > self.size as f64 {
-            return;
+    /// Removes every entry but keeps the buckets allocated.
+    pub fn clear(&mut self) {
+        for el in self.buckets.iter_mut() {
+            el.clear();
         }
+        self.old_buckets = Vec::new();
+        self.migrated = 0;
+        self.size = 0;
+    }
+
+    /// Keeps only the entries for which `f` returns `true`.
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&K, &mut V) -> bool,
+    {
+        for el in self.old_buckets.iter_mut().chain(self.buckets.iter_mut()) {
+            let removed = el.extract_if(|(k, v)| !f(k, v)).count();
+            self.size -= removed;
+        }
+        self.shrink();
+    }
+
+    /// Grows so that `additional` more entries fit without another resize.
+    /// Any rehash this starts, or one already running, is finished here.
+    pub fn reserve(&mut self, additional: usize) {
+        let needed = self.buckets_for(self.size + additional);
+        if needed > self.capacity {
+            self.rehash(needed);
+        }
+        self.migrate(usize::MAX);
+    }
+
+    /// Shrinks to the fewest buckets that hold the current entries, ignoring
+    /// the capacity the table was created with.
+    pub fn shrink_to_fit(&mut self) {
+        let needed = self.buckets_for(self.size);
+        if needed < self.capacity {
+            self.rehash(needed);
+        }
+        self.migrate(usize::MAX);
+    }
+
+    // Doubles once `extra` more entries would push the load past the grow
+    // factor.
+    //
+    // While a rehash runs, this and `shrink` wait for it to finish instead of
+    // forcing the rest of the migration. Every insert and remove checks
+    // again, and `MIGRATE_STEP` keeps the wait short.
+    fn grow(&mut self, extra: usize) {
+        if !self.is_rehashing()
+            && (self.size + extra) as f64 > self.capacity as f64 * self.grow_load_factor
+        {
+            self.rehash(self.capacity * 2);
+        }
+    }
+
+    // Halves once the load drops under the shrink factor.
+    fn shrink(&mut self) {
+        if !self.is_rehashing()
+            && self.capacity > self.min_capacity
+            && (self.size as f64) < self.capacity as f64 * self.shrink_load_factor
+        {
+            let capacity = (self.capacity / 2)
+                .max(self.min_capacity)
+                .max(self.buckets_for(self.size));
+            self.rehash(capacity);
+        }
+    }
 
-        self.capacity *= 2;
-        let mut new_buckets = Self::new_buckets(self.capacity);
-        for el in self.buckets.drain(..) {
-            for (k, v) in el.into_iter() {
-                Self::buckets_insert(&mut new_buckets, self.capacity, k, v);
+    // Switches to `capacity` buckets, moving the entries now or, in
+    // incremental mode, starting with a first step and going on over the
+    // next operations.
+    fn rehash(&mut self, capacity: usize) {
+        // only `reserve` and `shrink_to_fit` get here mid-rehash; they finish
+        // it anyway, and it must end before its buckets are replaced
+        self.migrate(usize::MAX);
+
+        self.capacity = capacity;
+        self.resizes += 1;
+        self.old_buckets = mem::replace(&mut self.buckets, Self::new_buckets(capacity));
+        self.migrated = 0;
+        self.migrate(if self.incremental {
+            MIGRATE_STEP
+        } else {
+            usize::MAX
+        });
+    }
+
+    // Moves up to `count` old buckets into the current ones.
+    fn migrate(&mut self, count: usize) {
+        for _ in 0..count {
+            let Some(el) = self.old_buckets.get_mut(self.migrated) else {
+                break;
+            };
+            for (k, v) in mem::take(el) {
+                let index = self.hash_index(&k, self.capacity);
+                self.buckets[index].push_back((k, v));
+            }
+
+            self.migrated += 1;
+            if self.migrated == self.old_buckets.len() {
+                self.old_buckets = Vec::new();
+                self.migrated = 0;
             }
         }
-        self.buckets = new_buckets; // replace the resized
+    }
+
+    // The bucket that holds `key`, or would.
+    fn bucket(&self, key: &K) -> &LinkedList<(K, V)> {
+        let hash = self.hash(key);
+        if self.is_rehashing() {
+            let index = hash % self.old_buckets.len();
+            if index >= self.migrated {
+                return &self.old_buckets[index];
+            }
+        }
+        &self.buckets[hash % self.capacity]
+    }
+
+    fn bucket_mut(&mut self, key: &K) -> &mut LinkedList<(K, V)> {
+        let hash = self.hash(key);
+        if self.is_rehashing() {
+            let index = hash % self.old_buckets.len();
+            if index >= self.migrated {
+                return &mut self.old_buckets[index];
+            }
+        }
+        &mut self.buckets[hash % self.capacity]
+    }
+}
+
+impl<K, V, S> Default for HashTable<K, V, S>
+where
+    K: Hash + PartialEq,
+    S: Default,
+{
+    fn default() -> Self {
+        Self::with_hasher(S::default())
+    }
+}
+
+impl<K, V, S> Index<&K> for HashTable<K, V, S>
+where
+    K: Hash + PartialEq,
+    S: BuildHasher,
+{
+    type Output = V;
+
+    /// # Panics
+    /// Panics if `key` is not in the table.
+    fn index(&self, key: &K) -> &V {
+        self.get(key).expect("key not found in HashTable")
     }
 }
 
 // Hash Function
-impl<K, V> HashTable<K, V>
+impl<K, V, S> HashTable<K, V, S>
 where
     K: Hash + PartialEq,
+    S: BuildHasher,
 {
-    pub fn hash_index(key: &K, capacity: usize) -> usize {
-        Self::hash(key) % capacity
+    pub fn hash_index(&self, key: &K, capacity: usize) -> usize {
+        self.hash(key) % capacity
     }
 
-    pub fn hash(key: &K) -> usize {
-        let mut hasher = DefaultHasher::new();
-        key.hash(&mut hasher);
-        hasher.finish() as usize
+    pub fn hash(&self, key: &K) -> usize {
+        hashers::hash_key(&self.hash_builder, key) as usize
     }
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
+    use test_util::Rng;
 
     #[test]
     fn test_hashtable_hash() {
         let key = "key".to_string();
+        let ht = HashTable::<String, String>::new(10);
         assert!(
-            HashTable::<String, String>::hash(&key) == HashTable::<String, String>::hash(&key),
+            ht.hash(&key) == ht.hash(&key),
             "hashed the same key must equal"
         );
     }
@@ -144,9 +419,9 @@ mod test {
     fn test_hashtable_hash_index() {
         let key = "key".to_string();
         let cap = 10;
+        let ht = HashTable::<String, String>::new(cap);
         assert!(
-            HashTable::<String, String>::hash_index(&key, cap)
-                == HashTable::<String, String>::hash_index(&key, cap),
+            ht.hash_index(&key, cap) == ht.hash_index(&key, cap),
             "hashed the same key must equal"
         );
     }
@@ -172,6 +447,16 @@ mod test {
         println!("{:?}", value);
     }
 
+    #[test]
+    fn test_hashtable_get_mut() {
+        let mut ht = HashTable::new(10);
+        ht.insert("hits", 1);
+
+        *ht.get_mut(&"hits").unwrap() += 1;
+        assert_eq!(ht.get(&"hits"), Some(&2));
+        assert_eq!(ht.get_mut(&"misses"), None);
+    }
+
     #[test]
     fn test_hashtable_remove() {
         let mut ht = HashTable::new(10);
@@ -224,4 +509,314 @@ mod test {
         assert_eq!(ht.get(&"c"), Some(&3));
         assert_eq!(ht.get(&"d"), Some(&4));
     }
+
+    #[test]
+    fn test_insert_returns_previous_value() {
+        let mut ht = HashTable::new(4);
+        assert_eq!(ht.insert("a", 1), None);
+        assert_eq!(ht.insert("a", 2), Some(1));
+        assert_eq!(ht.len(), 1);
+        assert!(ht.contains_key(&"a"));
+        assert!(!ht.contains_key(&"b"));
+    }
+
+    #[test]
+    fn test_clear() {
+        let mut ht = HashTable::new(4);
+        ht.insert(1, 1);
+        ht.insert(2, 2);
+        let capacity = ht.capacity;
+
+        ht.clear();
+        assert!(ht.is_empty());
+        assert_eq!(ht.get(&1), None);
+        assert_eq!(ht.capacity, capacity);
+    }
+
+    #[test]
+    fn test_retain() {
+        let mut ht = HashTable::new(4);
+        for key in 0..10 {
+            ht.insert(key, key);
+        }
+
+        ht.retain(|k, v| {
+            *v *= 10;
+            k % 2 == 0
+        });
+        assert_eq!(ht.len(), 5);
+        assert_eq!(ht.get(&4), Some(&40));
+        assert_eq!(ht.get(&5), None);
+    }
+
+    #[test]
+    fn test_index() {
+        let mut ht = HashTable::new(4);
+        ht.insert("a", 1);
+        assert_eq!(ht[&"a"], 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "key not found")]
+    fn test_index_missing_key() {
+        let ht: HashTable<&str, i32> = HashTable::new(4);
+        let _ = ht[&"a"];
+    }
+
+    fn sorted<K: Ord + Clone, V: Clone>(entries: impl Iterator<Item = (K, V)>) -> Vec<(K, V)> {
+        let mut entries: Vec<(K, V)> = entries.collect();
+        entries.sort_by(|a, b| a.0.cmp(&b.0));
+        entries
+    }
+
+    // Runs random operations on tables from `make` and a std map side by
+    // side.
+    fn check_against_std<F>(seed: u64, make: F)
+    where
+        F: Fn(&mut Rng) -> HashTable<u32, i32>,
+    {
+        use std::collections::HashMap;
+
+        let mut rng = Rng(seed);
+        for _ in 0..20 {
+            let mut ht = make(&mut rng);
+            let mut std = HashMap::new();
+
+            for step in 0..500 {
+                let key = rng.below(64) as u32;
+                match rng.below(9) {
+                    0 | 1 => assert_eq!(ht.insert(key, step), std.insert(key, step)),
+                    2 => assert_eq!(ht.remove(&key), std.remove(&key)),
+                    3 => assert_eq!(ht.get(&key), std.get(&key)),
+                    4 => {
+                        if let Some(v) = ht.get_mut(&key) {
+                            *v += 1;
+                        }
+                        if let Some(v) = std.get_mut(&key) {
+                            *v += 1;
+                        }
+                    }
+                    5 => assert_eq!(ht.contains_key(&key), std.contains_key(&key)),
+                    6 => {
+                        *ht.entry(key).or_insert(0) += 1;
+                        *std.entry(key).or_insert(0) += 1;
+                    }
+                    7 => {
+                        let modulus = rng.below(5) as u32 + 2;
+                        ht.retain(|k, _| k % modulus != 0);
+                        std.retain(|k, _| k % modulus != 0);
+                    }
+                    _ => {
+                        if rng.below(20) == 0 {
+                            let drained = sorted(ht.drain());
+                            assert_eq!(drained, sorted(std.drain()));
+                        }
+                    }
+                }
+                assert_eq!(ht.len(), std.len());
+                assert_eq!(ht.is_empty(), std.is_empty());
+                // a grow due mid-rehash waits for the migration to finish
+                assert!(
+                    ht.is_rehashing()
+                        || ht.len() as f64 <= ht.capacity as f64 * ht.grow_load_factor
+                );
+            }
+
+            for (key, value) in &std {
+                assert_eq!(ht[key], *value);
+            }
+            assert_eq!(sorted(ht.iter()), sorted(std.iter()));
+        }
+    }
+
+    #[test]
+    fn test_matches_std_hash_map() {
+        check_against_std(0x2545_f491_4f6c_dd1d, |rng| {
+            HashTable::new(rng.below(8) + 1)
+        });
+    }
+
+    #[test]
+    fn test_incremental_matches_std_hash_map() {
+        check_against_std(0x9e37_79b9_7f4a_7c15, |rng| {
+            let grow = [0.5, 0.75, 1.0, 3.0][rng.below(4)];
+            HashTable::new(rng.below(8))
+                .load_factors(grow, grow / 4.0)
+                .incremental_rehash(true)
+        });
+    }
+
+    #[test]
+    fn test_new_with_zero_capacity() {
+        let mut ht = HashTable::new(0);
+        assert_eq!(ht.get(&1), None);
+        assert_eq!(ht.remove(&1), None);
+
+        ht.insert(1, 10);
+        ht.insert(2, 20);
+        assert_eq!(ht.get(&1), Some(&10));
+        assert_eq!(ht.len(), 2);
+    }
+
+    #[test]
+    fn test_shrinks_after_removals() {
+        let mut ht = HashTable::new(8);
+        for key in 0..1000 {
+            ht.insert(key, key);
+        }
+        let grown = ht.capacity();
+
+        for key in 10..1000 {
+            ht.remove(&key);
+        }
+        assert!(ht.capacity() < grown / 10, "{}", ht.capacity());
+        // never below the capacity it started with
+        assert!(ht.capacity() >= 8);
+        for key in 0..10 {
+            assert_eq!(ht.get(&key), Some(&key));
+        }
+    }
+
+    #[test]
+    fn test_load_factors() {
+        let mut ht = HashTable::new(4).load_factors(2.0, 0.0);
+        for key in 0..8 {
+            ht.insert(key, ());
+        }
+        assert_eq!(ht.capacity(), 4);
+        ht.insert(8, ());
+        assert_eq!(ht.capacity(), 8);
+
+        // shrinking is off
+        for key in 0..9 {
+            ht.remove(&key);
+        }
+        assert_eq!(ht.capacity(), 8);
+    }
+
+    #[test]
+    #[should_panic(expected = "load factors must satisfy")]
+    fn test_load_factors_that_would_thrash() {
+        let _ = HashTable::<i32, i32>::new(4).load_factors(0.75, 0.5);
+    }
+
+    #[test]
+    fn test_reserve_and_shrink_to_fit() {
+        let mut ht = HashTable::new(4);
+        ht.reserve(100);
+        let capacity = ht.capacity();
+        assert!(capacity >= 134);
+
+        for key in 0..100 {
+            ht.insert(key, key);
+        }
+        assert_eq!(ht.capacity(), capacity);
+
+        for key in 3..100 {
+            ht.remove(&key);
+        }
+        ht.shrink_to_fit();
+        assert_eq!(ht.capacity(), 4);
+        assert_eq!(ht.len(), 3);
+        assert_eq!(ht.get(&2), Some(&2));
+    }
+
+    #[test]
+    fn test_incremental_rehash_moves_a_few_buckets_at_a_time() {
+        let mut ht = HashTable::new(64).incremental_rehash(true);
+        for key in 0..48 {
+            ht.insert(key, key);
+        }
+        assert!(!ht.is_rehashing());
+
+        // the 49th entry doubles the table but moves only a few buckets
+        ht.insert(48, 48);
+        assert_eq!(ht.capacity(), 128);
+        assert!(ht.is_rehashing());
+        assert_eq!(ht.migrated, MIGRATE_STEP);
+
+        // lookups, updates and iteration see both halves
+        for key in 0..49 {
+            assert_eq!(ht.get(&key), Some(&key));
+        }
+        assert_eq!(ht.insert(60, -1), None);
+        assert_eq!(ht.insert(0, -1), Some(0));
+        assert_eq!(ht.remove(&63), None);
+        assert_eq!(ht.remove(&1), Some(1));
+        assert_eq!(ht.iter().count(), 49);
+
+        while ht.is_rehashing() {
+            ht.remove(&1000);
+        }
+        assert!(ht.old_buckets.is_empty());
+        assert_eq!(ht.len(), 49);
+        assert_eq!(ht[&0], -1);
+    }
+
+    #[test]
+    fn test_overwrite_at_threshold_does_not_grow() {
+        let mut ht = HashTable::new(4);
+        for key in 0..3 {
+            ht.insert(key, key);
+        }
+
+        // a fourth entry would pass 0.75, replacing a value adds nothing
+        assert_eq!(ht.insert(0, 10), Some(0));
+        *ht.entry(1).or_insert(0) += 10;
+        assert_eq!(ht.capacity(), 4);
+        assert_eq!(ht.resizes, 0);
+
+        ht.entry(3).or_insert(3);
+        assert_eq!(ht.capacity(), 8);
+        assert_eq!(ht.resizes, 1);
+    }
+
+    #[test]
+    fn test_incremental_grow_then_mass_remove() {
+        let mut ht = HashTable::new(4).incremental_rehash(true);
+
+        // every operation moves at most `MIGRATE_STEP` old buckets, and a
+        // resize only starts once the previous one has finished
+        let check = |ht: &mut HashTable<u32, u32>, op: &dyn Fn(&mut HashTable<u32, u32>)| {
+            let resizes = ht.resizes;
+            let left = ht.old_buckets.len() - ht.migrated;
+            let migrated = ht.migrated;
+            op(ht);
+
+            if ht.resizes == resizes {
+                if ht.is_rehashing() {
+                    assert!(ht.migrated - migrated <= MIGRATE_STEP);
+                }
+            } else {
+                assert_eq!(ht.resizes, resizes + 1);
+                assert!(left <= MIGRATE_STEP);
+            }
+        };
+
+        for key in 0..1000 {
+            check(&mut ht, &|ht| {
+                ht.insert(key, key);
+            });
+        }
+        let grown = ht.capacity();
+        assert!(grown >= 1024);
+
+        for key in 0..995 {
+            check(&mut ht, &|ht| {
+                assert_eq!(ht.remove(&key), Some(key));
+            });
+        }
+        while ht.is_rehashing() {
+            check(&mut ht, &|ht| {
+                ht.remove(&u32::MAX);
+            });
+        }
+
+        assert!(ht.capacity() < grown);
+        assert_eq!(ht.len(), 5);
+        for key in 995..1000 {
+            assert_eq!(ht.get(&key), Some(&key));
+        }
+        assert_eq!(ht.iter().count(), 5);
+    }
 }
@@ -0,0 +1,132 @@
+use std::collections::HashMap;
+use std::hash::{BuildHasher, Hash};
+
+use crate::HashTable;
+
+/// The operations every table in this crate supports, so implementations can
+/// be swapped and benchmarked against each other.
+pub trait Map<K, V> {
+    /// Inserts `key`, returning the value it replaced if it was present.
+    fn insert(&mut self, key: K, value: V) -> Option<V>;
+
+    fn get(&self, key: &K) -> Option<&V>;
+
+    fn get_mut(&mut self, key: &K) -> Option<&mut V>;
+
+    fn remove(&mut self, key: &K) -> Option<V>;
+
+    fn len(&self) -> usize;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn contains_key(&self, key: &K) -> bool {
+        self.get(key).is_some()
+    }
+}
+
+impl<K, V, S> Map<K, V> for HashTable<K, V, S>
+where
+    K: Hash + PartialEq,
+    S: BuildHasher,
+{
+    fn insert(&mut self, key: K, value: V) -> Option<V> {
+        HashTable::insert(self, key, value)
+    }
+
+    fn get(&self, key: &K) -> Option<&V> {
+        HashTable::get(self, key)
+    }
+
+    fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+        HashTable::get_mut(self, key)
+    }
+
+    fn remove(&mut self, key: &K) -> Option<V> {
+        HashTable::remove(self, key)
+    }
+
+    fn len(&self) -> usize {
+        HashTable::len(self)
+    }
+}
+
+// Lets `std` be used as the baseline in tests and benchmarks.
+impl<K, V, S> Map<K, V> for HashMap<K, V, S>
+where
+    K: Hash + Eq,
+    S: BuildHasher,
+{
+    fn insert(&mut self, key: K, value: V) -> Option<V> {
+        HashMap::insert(self, key, value)
+    }
+
+    fn get(&self, key: &K) -> Option<&V> {
+        HashMap::get(self, key)
+    }
+
+    fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+        HashMap::get_mut(self, key)
+    }
+
+    fn remove(&mut self, key: &K) -> Option<V> {
+        HashMap::remove(self, key)
+    }
+
+    fn len(&self) -> usize {
+        HashMap::len(self)
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod test {
+    use super::*;
+
+    use test_util::Rng;
+
+    /// Runs a random mix of operations on `map` and on a std `HashMap` and
+    /// checks every result agrees.
+    pub fn check_against_std<M>(mut map: M, seed: u64)
+    where
+        M: Map<u32, u32>,
+    {
+        let mut rng = Rng(seed);
+        let mut std = HashMap::new();
+
+        for step in 0..5000 {
+            // a small key space keeps hits, misses and re-inserts frequent
+            let key = rng.below(300) as u32;
+            match rng.below(6) {
+                0 | 1 => assert_eq!(map.insert(key, step), std.insert(key, step)),
+                2 => assert_eq!(map.remove(&key), std.remove(&key)),
+                3 => {
+                    if let Some(v) = map.get_mut(&key) {
+                        *v += 1;
+                    }
+                    if let Some(v) = std.get_mut(&key) {
+                        *v += 1;
+                    }
+                }
+                4 => assert_eq!(map.contains_key(&key), std.contains_key(&key)),
+                _ => assert_eq!(map.get(&key), std.get(&key)),
+            }
+            assert_eq!(map.len(), std.len());
+        }
+
+        for (key, value) in &std {
+            assert_eq!(map.get(key), Some(value));
+        }
+        assert_eq!(map.is_empty(), std.is_empty());
+    }
+
+    #[test]
+    fn test_chaining_matches_std() {
+        check_against_std(HashTable::new(4), 1);
+    }
+
+    #[test]
+    fn test_std_through_trait() {
+        check_against_std(HashMap::new(), 2);
+    }
+}
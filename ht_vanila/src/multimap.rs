@@ -0,0 +1,369 @@
+//! A one-to-many map over [`HashTable`], keeping every key's values in a
+//! `Vec` in insertion order.
+
+use std::collections::hash_map::RandomState;
+use std::fmt;
+use std::hash::{BuildHasher, Hash};
+use std::iter::FusedIterator;
+use std::slice;
+
+use crate::HashTable;
+
+pub struct MultiMap<K, V, S = RandomState>
+where
+    K: Hash + PartialEq,
+{
+    // invariant: no key maps to an empty `Vec`
+    table: HashTable<K, Vec<V>, S>,
+    // values over all keys
+    len: usize,
+}
+
+impl<K, V> MultiMap<K, V, RandomState>
+where
+    K: Hash + PartialEq,
+{
+    pub fn new(capacity: usize) -> Self {
+        MultiMap {
+            table: HashTable::new(capacity),
+            len: 0,
+        }
+    }
+}
+
+impl<K, V, S> MultiMap<K, V, S>
+where
+    K: Hash + PartialEq,
+{
+    pub fn with_hasher(hash_builder: S) -> Self {
+        MultiMap {
+            table: HashTable::with_hasher(hash_builder),
+            len: 0,
+        }
+    }
+
+    pub fn with_capacity_and_hasher(capacity: usize, hash_builder: S) -> Self {
+        MultiMap {
+            table: HashTable::with_capacity_and_hasher(capacity, hash_builder),
+            len: 0,
+        }
+    }
+
+    /// Number of values over all keys.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = &K> {
+        self.table.keys()
+    }
+
+    /// Every key with all of its values.
+    pub fn iter_all(&self) -> impl Iterator<Item = (&K, &[V])> {
+        self.table.iter().map(|(k, values)| (k, values.as_slice()))
+    }
+
+    /// Every `(key, value)` pair; a key's values come out together, in
+    /// insertion order.
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        Iter {
+            entries: self.table.iter(),
+            current: None,
+            remaining: self.len,
+        }
+    }
+}
+
+impl<K, V, S> MultiMap<K, V, S>
+where
+    K: Hash + PartialEq,
+    S: BuildHasher,
+{
+    /// Number of distinct keys.
+    pub fn keys_len(&self) -> usize {
+        self.table.len()
+    }
+
+    /// Appends `value` to the values of `key`.
+    pub fn insert(&mut self, key: K, value: V) {
+        self.table.entry(key).or_default().push(value);
+        self.len += 1;
+    }
+
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.table.contains_key(key)
+    }
+
+    pub fn contains(&self, key: &K, value: &V) -> bool
+    where
+        V: PartialEq,
+    {
+        self.get(key).contains(value)
+    }
+
+    /// The values of `key` in insertion order, empty if it has none.
+    pub fn get(&self, key: &K) -> &[V] {
+        self.table.get(key).map_or(&[], |values| values.as_slice())
+    }
+
+    pub fn get_mut(&mut self, key: &K) -> Option<&mut [V]> {
+        self.table.get_mut(key).map(|values| values.as_mut_slice())
+    }
+
+    /// Removes `key` with all of its values.
+    pub fn remove(&mut self, key: &K) -> Vec<V> {
+        let values = self.table.remove(key).unwrap_or_default();
+        self.len -= values.len();
+        values
+    }
+
+    /// Removes the first value of `key` equal to `value`, dropping the key
+    /// once it has none left.
+    pub fn remove_value(&mut self, key: &K, value: &V) -> Option<V>
+    where
+        V: PartialEq,
+    {
+        let values = self.table.get_mut(key)?;
+        let index = values.iter().position(|v| v == value)?;
+        let removed = values.remove(index);
+        if values.is_empty() {
+            self.table.remove(key);
+        }
+        self.len -= 1;
+        Some(removed)
+    }
+
+    /// Keeps only the pairs for which `f` returns `true`.
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&K, &V) -> bool,
+    {
+        let mut len = 0;
+        self.table.retain(|k, values| {
+            values.retain(|v| f(k, v));
+            len += values.len();
+            !values.is_empty()
+        });
+        self.len = len;
+    }
+
+    pub fn clear(&mut self) {
+        self.table.clear();
+        self.len = 0;
+    }
+}
+
+impl<K, V, S> Clone for MultiMap<K, V, S>
+where
+    K: Hash + PartialEq + Clone,
+    V: Clone,
+    S: Clone,
+{
+    fn clone(&self) -> Self {
+        MultiMap {
+            table: self.table.clone(),
+            len: self.len,
+        }
+    }
+}
+
+impl<K, V, S> Default for MultiMap<K, V, S>
+where
+    K: Hash + PartialEq,
+    S: Default,
+{
+    fn default() -> Self {
+        MultiMap {
+            table: HashTable::default(),
+            len: 0,
+        }
+    }
+}
+
+impl<K, V, S> fmt::Debug for MultiMap<K, V, S>
+where
+    K: Hash + PartialEq + fmt::Debug,
+    V: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter_all()).finish()
+    }
+}
+
+impl<K, V, S> FromIterator<(K, V)> for MultiMap<K, V, S>
+where
+    K: Hash + PartialEq,
+    S: BuildHasher + Default,
+{
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut map = MultiMap::default();
+        map.extend(iter);
+        map
+    }
+}
+
+impl<K, V, S> Extend<(K, V)> for MultiMap<K, V, S>
+where
+    K: Hash + PartialEq,
+    S: BuildHasher,
+{
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        for (k, v) in iter {
+            self.insert(k, v);
+        }
+    }
+}
+
+impl<'a, K, V, S> IntoIterator for &'a MultiMap<K, V, S>
+where
+    K: Hash + PartialEq,
+{
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    fn into_iter(self) -> Iter<'a, K, V> {
+        self.iter()
+    }
+}
+
+/// Created by [`MultiMap::iter`].
+pub struct Iter<'a, K, V> {
+    entries: crate::Iter<'a, K, Vec<V>>,
+    current: Option<(&'a K, slice::Iter<'a, V>)>,
+    remaining: usize,
+}
+
+impl<'a, K, V> Iterator for Iter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<(&'a K, &'a V)> {
+        loop {
+            if let Some((k, values)) = &mut self.current
+                && let Some(v) = values.next()
+            {
+                self.remaining -= 1;
+                return Some((*k, v));
+            }
+            let (k, values) = self.entries.next()?;
+            self.current = Some((k, values.iter()));
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<K, V> ExactSizeIterator for Iter<'_, K, V> {}
+
+impl<K, V> FusedIterator for Iter<'_, K, V> {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::collections::HashMap;
+    use test_util::Rng;
+
+    #[test]
+    fn test_insert_and_get() {
+        let mut map = MultiMap::new(4);
+        map.insert("fruit", "apple");
+        map.insert("fruit", "pear");
+        map.insert("veg", "leek");
+
+        assert_eq!(map.get(&"fruit"), ["apple", "pear"]);
+        assert_eq!(map.get(&"nut"), [] as [&str; 0]);
+        assert_eq!(map.len(), 3);
+        assert_eq!(map.keys_len(), 2);
+        assert!(map.contains(&"veg", &"leek"));
+        assert!(!map.contains(&"veg", &"apple"));
+    }
+
+    #[test]
+    fn test_remove_and_remove_value() {
+        let mut map: MultiMap<i32, i32> =
+            [(1, 10), (1, 11), (1, 10), (2, 20)].into_iter().collect();
+
+        assert_eq!(map.remove_value(&1, &10), Some(10));
+        assert_eq!(map.get(&1), [11, 10]);
+        assert_eq!(map.remove_value(&1, &12), None);
+        assert_eq!(map.remove_value(&3, &10), None);
+
+        assert_eq!(map.remove_value(&2, &20), Some(20));
+        assert!(!map.contains_key(&2));
+
+        assert_eq!(map.remove(&1), vec![11, 10]);
+        assert_eq!(map.remove(&1), Vec::<i32>::new());
+        assert!(map.is_empty());
+        assert_eq!(map.keys_len(), 0);
+    }
+
+    #[test]
+    fn test_iter_and_retain() {
+        let mut map: MultiMap<i32, i32> = (0..12).map(|v| (v % 3, v)).collect();
+        assert_eq!(map.iter().len(), 12);
+
+        let mut pairs: Vec<(i32, i32)> = map.iter().map(|(&k, &v)| (k, v)).collect();
+        pairs.sort();
+        assert_eq!(pairs[..4], [(0, 0), (0, 3), (0, 6), (0, 9)]);
+
+        // drop every value of key 0 and the odd values of the rest
+        map.retain(|&k, &v| k != 0 && v % 2 == 0);
+        assert_eq!(map.len(), 4);
+        assert!(!map.contains_key(&0));
+        assert_eq!(map.get(&1), [4, 10]);
+
+        if let Some(values) = map.get_mut(&2) {
+            values[0] = -2;
+        }
+        assert_eq!(map.get(&2), [-2, 8]);
+    }
+
+    #[test]
+    fn test_debug() {
+        let mut map = MultiMap::new(4);
+        map.insert("a", 1);
+        map.insert("a", 2);
+        assert_eq!(format!("{:?}", map), r#"{"a": [1, 2]}"#);
+    }
+
+    #[test]
+    fn test_matches_model() {
+        let mut rng = Rng(0xdead_beef_cafe_f00d);
+        let mut below = |n: usize| rng.below(n) as i32;
+
+        let mut map = MultiMap::new(2);
+        let mut model: HashMap<i32, Vec<i32>> = HashMap::new();
+        for _ in 0..2000 {
+            let key = below(16);
+            let value = below(4);
+            match below(4) {
+                0 | 1 => {
+                    map.insert(key, value);
+                    model.entry(key).or_default().push(value);
+                }
+                2 => {
+                    let expected = model.get_mut(&key).and_then(|values| {
+                        let index = values.iter().position(|&v| v == value)?;
+                        Some(values.remove(index))
+                    });
+                    if model.get(&key).is_some_and(|values| values.is_empty()) {
+                        model.remove(&key);
+                    }
+                    assert_eq!(map.remove_value(&key, &value), expected);
+                }
+                _ => assert_eq!(map.remove(&key), model.remove(&key).unwrap_or_default()),
+            }
+
+            assert_eq!(map.keys_len(), model.len());
+            assert_eq!(map.len(), model.values().map(Vec::len).sum::<usize>());
+            for (key, values) in &model {
+                assert_eq!(map.get(key), values.as_slice());
+            }
+        }
+    }
+}
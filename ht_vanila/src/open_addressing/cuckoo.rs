@@ -0,0 +1,168 @@
+use std::hash::Hash;
+use std::mem;
+
+use super::{hash, slot_count};
+use crate::Map;
+
+// Displacements tried before giving up and rehashing.
+const MAX_KICKS: usize = 32;
+
+/// Cuckoo hashing over two arrays with independent hash functions. Every key
+/// lives in one of its two candidate slots, so lookups check at most two
+/// places. An insert that finds both taken kicks the occupant to its other
+/// slot, and so on; a chain that runs too long triggers a rehash with fresh
+/// seeds and more room.
+pub struct CuckooTable<K, V> {
+    tables: [Vec<Option<(K, V)>>; 2],
+    seeds: [u64; 2],
+    size: usize,
+}
+
+impl<K, V> CuckooTable<K, V>
+where
+    K: Hash + PartialEq,
+{
+    pub fn new(capacity: usize) -> Self {
+        // both halves together hold twice `capacity` slots, which matches
+        // the ~50% load cuckoo hashing tolerates
+        let count = slot_count(capacity);
+        CuckooTable {
+            tables: [Self::empty_slots(count), Self::empty_slots(count)],
+            seeds: [1, 2],
+            size: 0,
+        }
+    }
+
+    /// Total number of slots across both arrays.
+    pub fn capacity(&self) -> usize {
+        self.tables[0].len() * 2
+    }
+
+    fn empty_slots(count: usize) -> Vec<Option<(K, V)>> {
+        let mut slots = Vec::with_capacity(count);
+        slots.resize_with(count, || None);
+        slots
+    }
+
+    fn index(&self, table: usize, key: &K) -> usize {
+        hash(key, self.seeds[table]) as usize & (self.tables[table].len() - 1)
+    }
+
+    fn find(&self, key: &K) -> Option<(usize, usize)> {
+        (0..2).find_map(|table| {
+            let index = self.index(table, key);
+            match &self.tables[table][index] {
+                Some((k, _)) if k == key => Some((table, index)),
+                _ => None,
+            }
+        })
+    }
+
+    // Places an entry for a key that is not in the table, rehashing as
+    // needed.
+    fn place(&mut self, mut entry: (K, V)) {
+        loop {
+            for kick in 0..MAX_KICKS {
+                let table = kick % 2;
+                let index = self.index(table, &entry.0);
+                match self.tables[table][index].replace(entry) {
+                    None => return,
+                    Some(evicted) => entry = evicted,
+                }
+            }
+            self.rehash(self.tables[0].len() * 2);
+        }
+    }
+
+    fn rehash(&mut self, count: usize) {
+        let old = mem::replace(
+            &mut self.tables,
+            [Self::empty_slots(count), Self::empty_slots(count)],
+        );
+        // new hash functions, in case the old pair was what caused the cycle
+        self.seeds = [self.seeds[0] + 2, self.seeds[1] + 2];
+        for entry in old.into_iter().flatten().flatten() {
+            self.place(entry);
+        }
+    }
+}
+
+impl<K, V> Map<K, V> for CuckooTable<K, V>
+where
+    K: Hash + PartialEq,
+{
+    fn insert(&mut self, key: K, value: V) -> Option<V> {
+        if let Some(v) = self.get_mut(&key) {
+            return Some(mem::replace(v, value));
+        }
+
+        if (self.size + 1) * 2 > self.capacity() {
+            self.rehash(self.tables[0].len() * 2);
+        }
+        self.place((key, value));
+        self.size += 1;
+        None
+    }
+
+    fn get(&self, key: &K) -> Option<&V> {
+        let (table, index) = self.find(key)?;
+        self.tables[table][index].as_ref().map(|(_, v)| v)
+    }
+
+    fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+        let (table, index) = self.find(key)?;
+        self.tables[table][index].as_mut().map(|(_, v)| v)
+    }
+
+    fn remove(&mut self, key: &K) -> Option<V> {
+        let (table, index) = self.find(key)?;
+        let (_, value) = self.tables[table][index].take()?;
+        self.size -= 1;
+        Some(value)
+    }
+
+    fn len(&self) -> usize {
+        self.size
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::map::test::check_against_std;
+
+    #[test]
+    fn test_matches_std() {
+        check_against_std(CuckooTable::new(1), 31);
+        check_against_std(CuckooTable::new(1000), 32);
+    }
+
+    #[test]
+    fn test_every_key_in_one_of_two_slots() {
+        let mut table = CuckooTable::new(0);
+        for key in 0..2_000 {
+            table.insert(key, key);
+        }
+
+        for key in 0..2_000 {
+            let (t, index) = table.find(&key).unwrap();
+            assert_eq!(index, table.index(t, &key));
+        }
+        assert_eq!(table.len(), 2_000);
+        assert!(table.capacity() >= 4_000);
+    }
+
+    #[test]
+    fn test_remove_needs_no_tombstones() {
+        let mut table = CuckooTable::new(16);
+        for key in 0..10 {
+            table.insert(key, key);
+        }
+        for key in 0..10 {
+            assert_eq!(table.remove(&key), Some(key));
+            assert_eq!(table.get(&key), None);
+        }
+        assert!(table.is_empty());
+        assert!(table.tables.iter().flatten().all(Option::is_none));
+    }
+}
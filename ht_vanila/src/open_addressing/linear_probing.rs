@@ -0,0 +1,207 @@
+use std::hash::Hash;
+use std::mem;
+
+use super::{hash, slot_count};
+use crate::Map;
+
+enum Slot<K, V> {
+    Empty,
+    // a removed entry; probes continue past it so later keys stay reachable
+    Tombstone,
+    Full(K, V),
+}
+
+/// Open addressing with linear probing. Removal leaves a tombstone, and
+/// tombstones are cleared whenever the table rehashes.
+pub struct LinearProbingTable<K, V> {
+    slots: Vec<Slot<K, V>>,
+    size: usize,
+    tombstones: usize,
+}
+
+impl<K, V> LinearProbingTable<K, V>
+where
+    K: Hash + PartialEq,
+{
+    pub fn new(capacity: usize) -> Self {
+        LinearProbingTable {
+            slots: Self::empty_slots(slot_count(capacity)),
+            size: 0,
+            tombstones: 0,
+        }
+    }
+
+    /// Number of slots, counting empty ones and tombstones.
+    pub fn capacity(&self) -> usize {
+        self.slots.len()
+    }
+
+    pub fn tombstones(&self) -> usize {
+        self.tombstones
+    }
+
+    fn empty_slots(count: usize) -> Vec<Slot<K, V>> {
+        let mut slots = Vec::with_capacity(count);
+        slots.resize_with(count, || Slot::Empty);
+        slots
+    }
+
+    fn home(&self, key: &K) -> usize {
+        hash(key, 0) as usize & (self.slots.len() - 1)
+    }
+
+    fn find(&self, key: &K) -> Option<usize> {
+        let mask = self.slots.len() - 1;
+        let mut index = self.home(key);
+        // the load limit guarantees an empty slot, so this terminates
+        loop {
+            match &self.slots[index] {
+                Slot::Empty => return None,
+                Slot::Full(k, _) if k == key => return Some(index),
+                _ => index = (index + 1) & mask,
+            }
+        }
+    }
+
+    // Keeps live entries plus tombstones at or below 3/4 of the slots.
+    fn reserve_one(&mut self) {
+        let used = self.size + self.tombstones + 1;
+        if used * 4 <= self.slots.len() * 3 {
+            return;
+        }
+
+        // mostly tombstones: clean up at the same size instead of growing
+        let count = if (self.size + 1) * 2 <= self.slots.len() {
+            self.slots.len()
+        } else {
+            self.slots.len() * 2
+        };
+        self.rehash(count);
+    }
+
+    fn rehash(&mut self, count: usize) {
+        let old = mem::replace(&mut self.slots, Self::empty_slots(count));
+        self.tombstones = 0;
+        for slot in old {
+            if let Slot::Full(key, value) = slot {
+                let index = self.free_slot(&key);
+                self.slots[index] = Slot::Full(key, value);
+            }
+        }
+    }
+
+    // First empty or tombstone slot on `key`'s probe sequence.
+    fn free_slot(&self, key: &K) -> usize {
+        let mask = self.slots.len() - 1;
+        let mut index = self.home(key);
+        while let Slot::Full(..) = self.slots[index] {
+            index = (index + 1) & mask;
+        }
+        index
+    }
+}
+
+impl<K, V> Map<K, V> for LinearProbingTable<K, V>
+where
+    K: Hash + PartialEq,
+{
+    fn insert(&mut self, key: K, value: V) -> Option<V> {
+        if let Some(v) = self.get_mut(&key) {
+            return Some(mem::replace(v, value));
+        }
+
+        self.reserve_one();
+        let index = self.free_slot(&key);
+        if let Slot::Tombstone = self.slots[index] {
+            self.tombstones -= 1;
+        }
+        self.slots[index] = Slot::Full(key, value);
+        self.size += 1;
+        None
+    }
+
+    fn get(&self, key: &K) -> Option<&V> {
+        match &self.slots[self.find(key)?] {
+            Slot::Full(_, v) => Some(v),
+            _ => None,
+        }
+    }
+
+    fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+        let index = self.find(key)?;
+        match &mut self.slots[index] {
+            Slot::Full(_, v) => Some(v),
+            _ => None,
+        }
+    }
+
+    fn remove(&mut self, key: &K) -> Option<V> {
+        let index = self.find(key)?;
+        match mem::replace(&mut self.slots[index], Slot::Tombstone) {
+            Slot::Full(_, v) => {
+                self.size -= 1;
+                self.tombstones += 1;
+                Some(v)
+            }
+            _ => None,
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.size
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::map::test::check_against_std;
+
+    #[test]
+    fn test_matches_std() {
+        check_against_std(LinearProbingTable::new(1), 11);
+        check_against_std(LinearProbingTable::new(1000), 12);
+    }
+
+    #[test]
+    fn test_remove_leaves_tombstone() {
+        let mut table = LinearProbingTable::new(8);
+        for key in 0..5 {
+            table.insert(key, key);
+        }
+
+        assert_eq!(table.remove(&2), Some(2));
+        assert_eq!(table.remove(&2), None);
+        assert_eq!(table.tombstones(), 1);
+
+        // keys probed past the tombstone are still found
+        for key in [0, 1, 3, 4] {
+            assert_eq!(table.get(&key), Some(&key));
+        }
+    }
+
+    #[test]
+    fn test_churn_does_not_grow() {
+        let mut table = LinearProbingTable::new(16);
+        for key in 0..10_000 {
+            table.insert(key, key);
+            table.remove(&key);
+        }
+
+        // tombstones are swept by same-size rehashes instead of doubling
+        assert_eq!(table.capacity(), 16);
+        assert!(table.is_empty());
+    }
+
+    #[test]
+    fn test_grows() {
+        let mut table = LinearProbingTable::new(0);
+        for key in 0..100 {
+            table.insert(key, key * 2);
+        }
+
+        assert!(table.capacity() >= 128);
+        assert_eq!(table.len(), 100);
+        assert_eq!(table.get(&99), Some(&198));
+    }
+}
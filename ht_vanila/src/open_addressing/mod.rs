@@ -0,0 +1,28 @@
+//! Tables that store entries directly in one flat array instead of a list
+//! per bucket. All of them keep a power-of-two slot count so the home slot is
+//! `hash & mask`.
+
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+mod cuckoo;
+mod linear_probing;
+mod robin_hood;
+
+pub use cuckoo::CuckooTable;
+pub use linear_probing::LinearProbingTable;
+pub use robin_hood::RobinHoodTable;
+
+// Smallest slot count any table starts with.
+const MIN_SLOTS: usize = 8;
+
+fn hash<K: Hash>(key: &K, seed: u64) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    seed.hash(&mut hasher);
+    key.hash(&mut hasher);
+    hasher.finish()
+}
+
+fn slot_count(capacity: usize) -> usize {
+    capacity.next_power_of_two().max(MIN_SLOTS)
+}
@@ -0,0 +1,231 @@
+use std::hash::Hash;
+use std::mem;
+
+use super::{hash, slot_count};
+use crate::Map;
+
+struct Entry<K, V> {
+    hash: u64,
+    key: K,
+    value: V,
+}
+
+/// Open addressing with Robin Hood hashing: an insert takes the slot of any
+/// entry that sits closer to its home slot than the new one would, which keeps
+/// probe lengths short and even. Removal shifts the following entries back
+/// instead of leaving tombstones.
+pub struct RobinHoodTable<K, V> {
+    slots: Vec<Option<Entry<K, V>>>,
+    size: usize,
+}
+
+impl<K, V> RobinHoodTable<K, V>
+where
+    K: Hash + PartialEq,
+{
+    pub fn new(capacity: usize) -> Self {
+        RobinHoodTable {
+            slots: Self::empty_slots(slot_count(capacity)),
+            size: 0,
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.slots.len()
+    }
+
+    /// Longest distance of any entry from its home slot.
+    pub fn max_probe_distance(&self) -> usize {
+        self.slots
+            .iter()
+            .enumerate()
+            .filter_map(|(index, slot)| slot.as_ref().map(|e| self.distance(index, e.hash)))
+            .max()
+            .unwrap_or(0)
+    }
+
+    fn empty_slots(count: usize) -> Vec<Option<Entry<K, V>>> {
+        let mut slots = Vec::with_capacity(count);
+        slots.resize_with(count, || None);
+        slots
+    }
+
+    fn mask(&self) -> usize {
+        self.slots.len() - 1
+    }
+
+    // How far slot `index` is from the home slot of `hash`.
+    fn distance(&self, index: usize, hash: u64) -> usize {
+        index.wrapping_sub(hash as usize) & self.mask()
+    }
+
+    fn find(&self, key: &K) -> Option<usize> {
+        let hash = hash(key, 0);
+        let mut index = hash as usize & self.mask();
+        let mut distance = 0;
+        loop {
+            let entry = self.slots[index].as_ref()?;
+            // every entry past this point is closer to home than `key` would
+            // be, so `key` cannot be further along
+            if self.distance(index, entry.hash) < distance {
+                return None;
+            }
+            if entry.hash == hash && entry.key == *key {
+                return Some(index);
+            }
+            index = (index + 1) & self.mask();
+            distance += 1;
+        }
+    }
+
+    // Places an entry for a key that is not in the table.
+    fn place(&mut self, mut entry: Entry<K, V>) {
+        let mask = self.mask();
+        let mut index = entry.hash as usize & mask;
+        let mut distance = 0;
+        loop {
+            match &mut self.slots[index] {
+                None => {
+                    self.slots[index] = Some(entry);
+                    return;
+                }
+                Some(existing) => {
+                    let existing_distance = index.wrapping_sub(existing.hash as usize) & mask;
+                    if existing_distance < distance {
+                        // take from the rich: the new entry is further from
+                        // home, so it gets this slot and the old one moves on
+                        mem::swap(existing, &mut entry);
+                        distance = existing_distance;
+                    }
+                }
+            }
+            index = (index + 1) & mask;
+            distance += 1;
+        }
+    }
+
+    fn grow(&mut self) {
+        let count = self.slots.len() * 2;
+        let old = mem::replace(&mut self.slots, Self::empty_slots(count));
+        for entry in old.into_iter().flatten() {
+            self.place(entry);
+        }
+    }
+}
+
+impl<K, V> Map<K, V> for RobinHoodTable<K, V>
+where
+    K: Hash + PartialEq,
+{
+    fn insert(&mut self, key: K, value: V) -> Option<V> {
+        if let Some(v) = self.get_mut(&key) {
+            return Some(mem::replace(v, value));
+        }
+
+        // Robin Hood copes with high load, grow past 7/8
+        if (self.size + 1) * 8 > self.slots.len() * 7 {
+            self.grow();
+        }
+        let hash = hash(&key, 0);
+        self.place(Entry { hash, key, value });
+        self.size += 1;
+        None
+    }
+
+    fn get(&self, key: &K) -> Option<&V> {
+        let index = self.find(key)?;
+        self.slots[index].as_ref().map(|entry| &entry.value)
+    }
+
+    fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+        let index = self.find(key)?;
+        self.slots[index].as_mut().map(|entry| &mut entry.value)
+    }
+
+    fn remove(&mut self, key: &K) -> Option<V> {
+        let mut index = self.find(key)?;
+        let removed = self.slots[index].take()?;
+        self.size -= 1;
+
+        // backward shift: pull every following displaced entry one slot
+        // closer to home, stopping at a gap or an entry already at home
+        loop {
+            let next = (index + 1) & self.mask();
+            match &self.slots[next] {
+                Some(entry) if self.distance(next, entry.hash) > 0 => {
+                    self.slots[index] = self.slots[next].take();
+                    index = next;
+                }
+                _ => break,
+            }
+        }
+
+        Some(removed.value)
+    }
+
+    fn len(&self) -> usize {
+        self.size
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::map::test::check_against_std;
+
+    #[test]
+    fn test_matches_std() {
+        check_against_std(RobinHoodTable::new(1), 21);
+        check_against_std(RobinHoodTable::new(1000), 22);
+    }
+
+    // Every entry must be reachable without passing a gap, and no entry may
+    // sit further from home than the entry before it plus one.
+    fn assert_invariant<K: Hash + PartialEq, V>(table: &RobinHoodTable<K, V>) {
+        let n = table.slots.len();
+        for index in 0..n {
+            if let Some(entry) = &table.slots[index] {
+                let distance = table.distance(index, entry.hash);
+                let prev = (index + n - 1) & table.mask();
+                if distance > 0 {
+                    let prev_entry = table.slots[prev]
+                        .as_ref()
+                        .expect("no gap before a displaced entry");
+                    assert!(table.distance(prev, prev_entry.hash) + 1 >= distance);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_backward_shift_keeps_invariant() {
+        let mut table = RobinHoodTable::new(64);
+        for key in 0..50 {
+            table.insert(key, key);
+        }
+        assert_invariant(&table);
+
+        for key in (0..50).step_by(3) {
+            assert_eq!(table.remove(&key), Some(key));
+            assert_invariant(&table);
+        }
+
+        for key in 0..50 {
+            let expected = (key % 3 != 0).then_some(key);
+            assert_eq!(table.get(&key).copied(), expected);
+        }
+    }
+
+    #[test]
+    fn test_probe_distance_stays_small() {
+        let mut table = RobinHoodTable::new(0);
+        for key in 0..10_000 {
+            table.insert(key, ());
+        }
+
+        assert_eq!(table.len(), 10_000);
+        // loose bound; with a decent hash the longest probe is tiny compared
+        // to the table
+        assert!(table.max_probe_distance() < 64);
+    }
+}
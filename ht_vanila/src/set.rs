@@ -0,0 +1,482 @@
+//! A set over [`HashTable`], storing each element as a key with a `()`
+//! value.
+
+use std::collections::hash_map::RandomState;
+use std::fmt;
+use std::hash::{BuildHasher, Hash};
+use std::iter::{Chain, FusedIterator};
+
+use crate::{HashTable, Keys};
+
+pub struct HashSet<T, S = RandomState>
+where
+    T: Hash + PartialEq,
+{
+    table: HashTable<T, (), S>,
+}
+
+impl<T> HashSet<T, RandomState>
+where
+    T: Hash + PartialEq,
+{
+    pub fn new(capacity: usize) -> Self {
+        HashSet {
+            table: HashTable::new(capacity),
+        }
+    }
+}
+
+impl<T, S> HashSet<T, S>
+where
+    T: Hash + PartialEq,
+{
+    pub fn with_hasher(hash_builder: S) -> Self {
+        HashSet {
+            table: HashTable::with_hasher(hash_builder),
+        }
+    }
+
+    pub fn with_capacity_and_hasher(capacity: usize, hash_builder: S) -> Self {
+        HashSet {
+            table: HashTable::with_capacity_and_hasher(capacity, hash_builder),
+        }
+    }
+
+    pub fn hasher(&self) -> &S {
+        self.table.hasher()
+    }
+
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            keys: self.table.keys(),
+        }
+    }
+}
+
+impl<T, S> HashSet<T, S>
+where
+    T: Hash + PartialEq,
+    S: BuildHasher,
+{
+    pub fn len(&self) -> usize {
+        self.table.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.table.is_empty()
+    }
+
+    /// Adds `value`, returning whether it was new.
+    pub fn insert(&mut self, value: T) -> bool {
+        self.table.insert(value, ()).is_none()
+    }
+
+    pub fn contains(&self, value: &T) -> bool {
+        self.table.contains_key(value)
+    }
+
+    /// Removes `value`, returning whether it was present.
+    pub fn remove(&mut self, value: &T) -> bool {
+        self.table.remove(value).is_some()
+    }
+
+    pub fn clear(&mut self) {
+        self.table.clear();
+    }
+
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&T) -> bool,
+    {
+        self.table.retain(|value, _| f(value));
+    }
+
+    /// Elements in `self`, then those only in `other`.
+    pub fn union<'a>(&'a self, other: &'a Self) -> Union<'a, T, S> {
+        Union {
+            inner: self.iter().chain(other.difference(self)),
+        }
+    }
+
+    pub fn intersection<'a>(&'a self, other: &'a Self) -> Intersection<'a, T, S> {
+        // walk the smaller set, probe the larger
+        let (small, large) = if self.len() <= other.len() {
+            (self, other)
+        } else {
+            (other, self)
+        };
+        Intersection {
+            iter: small.iter(),
+            other: large,
+        }
+    }
+
+    /// Elements in `self` but not in `other`.
+    pub fn difference<'a>(&'a self, other: &'a Self) -> Difference<'a, T, S> {
+        Difference {
+            iter: self.iter(),
+            other,
+        }
+    }
+
+    /// Elements in exactly one of the two sets.
+    pub fn symmetric_difference<'a>(&'a self, other: &'a Self) -> SymmetricDifference<'a, T, S> {
+        SymmetricDifference {
+            inner: self.difference(other).chain(other.difference(self)),
+        }
+    }
+
+    pub fn is_subset(&self, other: &Self) -> bool {
+        self.len() <= other.len() && self.iter().all(|value| other.contains(value))
+    }
+
+    pub fn is_superset(&self, other: &Self) -> bool {
+        other.is_subset(self)
+    }
+
+    pub fn is_disjoint(&self, other: &Self) -> bool {
+        self.intersection(other).next().is_none()
+    }
+}
+
+impl<T, S> Clone for HashSet<T, S>
+where
+    T: Hash + PartialEq + Clone,
+    S: Clone,
+{
+    fn clone(&self) -> Self {
+        HashSet {
+            table: self.table.clone(),
+        }
+    }
+}
+
+impl<T, S> Default for HashSet<T, S>
+where
+    T: Hash + PartialEq,
+    S: Default,
+{
+    fn default() -> Self {
+        HashSet {
+            table: HashTable::default(),
+        }
+    }
+}
+
+impl<T, S> PartialEq for HashSet<T, S>
+where
+    T: Hash + PartialEq,
+    S: BuildHasher,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.is_subset(other)
+    }
+}
+
+impl<T, S> Eq for HashSet<T, S>
+where
+    T: Hash + Eq,
+    S: BuildHasher,
+{
+}
+
+impl<T, S> fmt::Debug for HashSet<T, S>
+where
+    T: Hash + PartialEq + fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+impl<T, S> FromIterator<T> for HashSet<T, S>
+where
+    T: Hash + PartialEq,
+    S: BuildHasher + Default,
+{
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        HashSet {
+            table: iter.into_iter().map(|value| (value, ())).collect(),
+        }
+    }
+}
+
+impl<T, S> Extend<T> for HashSet<T, S>
+where
+    T: Hash + PartialEq,
+    S: BuildHasher,
+{
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        self.table.extend(iter.into_iter().map(|value| (value, ())));
+    }
+}
+
+impl<T, S> IntoIterator for HashSet<T, S>
+where
+    T: Hash + PartialEq,
+{
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> IntoIter<T> {
+        IntoIter {
+            inner: self.table.into_iter(),
+        }
+    }
+}
+
+impl<'a, T, S> IntoIterator for &'a HashSet<T, S>
+where
+    T: Hash + PartialEq,
+{
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+/// Created by [`HashSet::iter`].
+pub struct Iter<'a, T> {
+    keys: Keys<'a, T, ()>,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        self.keys.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.keys.size_hint()
+    }
+}
+
+impl<T> ExactSizeIterator for Iter<'_, T> {}
+
+impl<T> FusedIterator for Iter<'_, T> {}
+
+/// Created by [`HashSet::into_iter`].
+pub struct IntoIter<T> {
+    inner: crate::IntoIter<T, ()>,
+}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.inner.next().map(|(value, _)| value)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<T> ExactSizeIterator for IntoIter<T> {}
+
+impl<T> FusedIterator for IntoIter<T> {}
+
+/// Created by [`HashSet::union`].
+pub struct Union<'a, T, S>
+where
+    T: Hash + PartialEq,
+{
+    inner: Chain<Iter<'a, T>, Difference<'a, T, S>>,
+}
+
+impl<'a, T, S> Iterator for Union<'a, T, S>
+where
+    T: Hash + PartialEq,
+    S: BuildHasher,
+{
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        self.inner.next()
+    }
+}
+
+/// Created by [`HashSet::intersection`].
+pub struct Intersection<'a, T, S>
+where
+    T: Hash + PartialEq,
+{
+    iter: Iter<'a, T>,
+    other: &'a HashSet<T, S>,
+}
+
+impl<'a, T, S> Iterator for Intersection<'a, T, S>
+where
+    T: Hash + PartialEq,
+    S: BuildHasher,
+{
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        let other = self.other;
+        self.iter.by_ref().find(|value| other.contains(value))
+    }
+}
+
+/// Created by [`HashSet::difference`].
+pub struct Difference<'a, T, S>
+where
+    T: Hash + PartialEq,
+{
+    iter: Iter<'a, T>,
+    other: &'a HashSet<T, S>,
+}
+
+impl<'a, T, S> Iterator for Difference<'a, T, S>
+where
+    T: Hash + PartialEq,
+    S: BuildHasher,
+{
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        let other = self.other;
+        self.iter.by_ref().find(|value| !other.contains(value))
+    }
+}
+
+/// Created by [`HashSet::symmetric_difference`].
+pub struct SymmetricDifference<'a, T, S>
+where
+    T: Hash + PartialEq,
+{
+    inner: Chain<Difference<'a, T, S>, Difference<'a, T, S>>,
+}
+
+impl<'a, T, S> Iterator for SymmetricDifference<'a, T, S>
+where
+    T: Hash + PartialEq,
+    S: BuildHasher,
+{
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        self.inner.next()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use test_util::Rng;
+
+    fn set(values: &[i32]) -> HashSet<i32> {
+        values.iter().copied().collect()
+    }
+
+    fn sorted<'a>(values: impl Iterator<Item = &'a i32>) -> Vec<i32> {
+        let mut values: Vec<i32> = values.copied().collect();
+        values.sort();
+        values
+    }
+
+    #[test]
+    fn test_insert_contains_remove() {
+        let mut set = HashSet::new(4);
+        assert!(set.insert("a"));
+        assert!(!set.insert("a"));
+        assert!(set.contains(&"a"));
+        assert_eq!(set.len(), 1);
+
+        assert!(set.remove(&"a"));
+        assert!(!set.remove(&"a"));
+        assert!(set.is_empty());
+    }
+
+    #[test]
+    fn test_set_operations() {
+        let a = set(&[1, 2, 3, 4]);
+        let b = set(&[3, 4, 5]);
+
+        assert_eq!(sorted(a.union(&b)), vec![1, 2, 3, 4, 5]);
+        assert_eq!(sorted(a.intersection(&b)), vec![3, 4]);
+        assert_eq!(sorted(b.intersection(&a)), vec![3, 4]);
+        assert_eq!(sorted(a.difference(&b)), vec![1, 2]);
+        assert_eq!(sorted(b.difference(&a)), vec![5]);
+        assert_eq!(sorted(a.symmetric_difference(&b)), vec![1, 2, 5]);
+    }
+
+    #[test]
+    fn test_subset_superset_disjoint() {
+        let small = set(&[1, 2]);
+        let large = set(&[1, 2, 3]);
+
+        assert!(small.is_subset(&large));
+        assert!(!large.is_subset(&small));
+        assert!(large.is_superset(&small));
+        assert!(small.is_subset(&small));
+        assert!(HashSet::new(1).is_subset(&small));
+
+        assert!(!small.is_disjoint(&large));
+        assert!(small.is_disjoint(&set(&[3, 4])));
+    }
+
+    #[test]
+    fn test_eq_ignores_order_and_capacity() {
+        let mut a = HashSet::new(1);
+        let mut b = HashSet::new(64);
+        for value in 0..10 {
+            a.insert(value);
+            b.insert(9 - value);
+        }
+        assert_eq!(a, b);
+
+        b.remove(&0);
+        assert_ne!(a, b);
+    }
+
+    #[test]
+    fn test_retain_extend_and_debug() {
+        let mut set = set(&[1, 2, 3, 4]);
+        set.retain(|value| value % 2 == 0);
+        set.extend([6, 2]);
+        assert_eq!(sorted(set.iter()), vec![2, 4, 6]);
+
+        let mut single = HashSet::new(4);
+        single.insert(7);
+        assert_eq!(format!("{:?}", single), "{7}");
+
+        let mut owned: Vec<i32> = set.into_iter().collect();
+        owned.sort();
+        assert_eq!(owned, vec![2, 4, 6]);
+    }
+
+    fn random_sets(rng: &mut Rng) -> (HashSet<i32>, std::collections::HashSet<i32>) {
+        let values = rng.vec(19, 30);
+        (
+            values.iter().copied().collect(),
+            values.iter().copied().collect(),
+        )
+    }
+
+    #[test]
+    fn test_operations_match_std() {
+        let mut rng = Rng(0x1234_5678_9abc_def1);
+        for _ in 0..200 {
+            let (a, std_a) = random_sets(&mut rng);
+            let (b, std_b) = random_sets(&mut rng);
+
+            assert_eq!(sorted(a.union(&b)), sorted(std_a.union(&std_b)));
+            assert_eq!(
+                sorted(a.intersection(&b)),
+                sorted(std_a.intersection(&std_b))
+            );
+            assert_eq!(sorted(a.difference(&b)), sorted(std_a.difference(&std_b)));
+            assert_eq!(
+                sorted(a.symmetric_difference(&b)),
+                sorted(std_a.symmetric_difference(&std_b))
+            );
+            assert_eq!(a.is_subset(&b), std_a.is_subset(&std_b));
+            assert_eq!(a.is_disjoint(&b), std_a.is_disjoint(&std_b));
+            assert_eq!(a == b, std_a == std_b);
+        }
+    }
+}
@@ -0,0 +1,331 @@
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hash};
+use std::sync::{PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+use crate::{Entry, HashTable, hashers};
+
+/// A map shared between threads, split over independently locked
+/// [`HashTable`] shards so operations on different shards never wait for
+/// each other.
+///
+/// Every operation locks exactly one shard, so each is atomic on its own.
+/// `len` and `clear` visit the shards one at a time and are not.
+pub struct ShardedHashTable<K, V, S = RandomState>
+where
+    K: Hash + PartialEq,
+{
+    shards: Vec<RwLock<HashTable<K, V, S>>>,
+    hash_builder: S,
+}
+
+impl<K, V> ShardedHashTable<K, V, RandomState>
+where
+    K: Hash + PartialEq,
+{
+    /// # Panics
+    /// Panics if `shards` is 0.
+    pub fn new(shards: usize) -> Self {
+        Self::with_hasher(shards, RandomState::new())
+    }
+}
+
+impl<K, V, S> ShardedHashTable<K, V, S>
+where
+    K: Hash + PartialEq,
+    S: BuildHasher + Clone,
+{
+    /// Every shard hashes with a clone of `hash_builder`.
+    ///
+    /// # Panics
+    /// Panics if `shards` is 0.
+    pub fn with_hasher(shards: usize, hash_builder: S) -> Self {
+        assert!(shards > 0, "shard count must be greater than zero");
+        ShardedHashTable {
+            shards: (0..shards)
+                .map(|_| RwLock::new(HashTable::with_hasher(hash_builder.clone())))
+                .collect(),
+            hash_builder,
+        }
+    }
+
+    pub fn shard_count(&self) -> usize {
+        self.shards.len()
+    }
+
+    pub fn get(&self, key: &K) -> Option<V>
+    where
+        V: Clone,
+    {
+        self.read(key).get(key).cloned()
+    }
+
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.read(key).contains_key(key)
+    }
+
+    /// Inserts `key`, returning the value it replaced if it was present.
+    pub fn insert(&self, key: K, value: V) -> Option<V> {
+        self.write(&key).insert(key, value)
+    }
+
+    pub fn remove(&self, key: &K) -> Option<V> {
+        self.write(key).remove(key)
+    }
+
+    /// Replaces the value of `key` with `f` of the current one, all under
+    /// the shard's lock. `f` returning `None` removes the key. Returns the
+    /// value that was replaced or removed.
+    pub fn compute<F>(&self, key: K, f: F) -> Option<V>
+    where
+        F: FnOnce(Option<&V>) -> Option<V>,
+    {
+        let mut shard = self.write(&key);
+        match shard.entry(key) {
+            Entry::Occupied(mut entry) => match f(Some(entry.get())) {
+                Some(value) => Some(entry.insert(value)),
+                None => Some(entry.remove()),
+            },
+            Entry::Vacant(entry) => {
+                if let Some(value) = f(None) {
+                    entry.insert(value);
+                }
+                None
+            }
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.shards.iter().map(|shard| lock_read(shard).len()).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.shards.iter().all(|shard| lock_read(shard).is_empty())
+    }
+
+    pub fn clear(&self) {
+        for shard in &self.shards {
+            lock_write(shard).clear();
+        }
+    }
+
+    // Shards take the high half of the same hash the tables inside use; they
+    // index their buckets with the low bits, which are then still spread
+    // evenly.
+    fn shard(&self, key: &K) -> &RwLock<HashTable<K, V, S>> {
+        let hash = hashers::hash_key(&self.hash_builder, key) as usize;
+        &self.shards[hash.rotate_right(usize::BITS / 2) % self.shards.len()]
+    }
+
+    fn read(&self, key: &K) -> RwLockReadGuard<'_, HashTable<K, V, S>> {
+        lock_read(self.shard(key))
+    }
+
+    fn write(&self, key: &K) -> RwLockWriteGuard<'_, HashTable<K, V, S>> {
+        lock_write(self.shard(key))
+    }
+}
+
+// A panic in a `compute` callback poisons the lock, but it happens before the
+// table is touched, so the shard is still consistent.
+fn lock_read<T>(lock: &RwLock<T>) -> RwLockReadGuard<'_, T> {
+    lock.read().unwrap_or_else(PoisonError::into_inner)
+}
+
+fn lock_write<T>(lock: &RwLock<T>) -> RwLockWriteGuard<'_, T> {
+    lock.write().unwrap_or_else(PoisonError::into_inner)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+    use std::thread;
+    use test_util::Rng;
+
+    const THREADS: usize = 8;
+
+    #[test]
+    fn test_single_thread() {
+        let map = ShardedHashTable::new(4);
+        assert_eq!(map.insert("a", 1), None);
+        assert_eq!(map.insert("a", 2), Some(1));
+        assert_eq!(map.get(&"a"), Some(2));
+        assert!(map.contains_key(&"a"));
+        assert_eq!(map.len(), 1);
+
+        assert_eq!(map.remove(&"a"), Some(2));
+        assert_eq!(map.get(&"a"), None);
+        assert!(map.is_empty());
+    }
+
+    #[test]
+    fn test_compute() {
+        let map = ShardedHashTable::new(2);
+        assert_eq!(map.compute("a", |v| Some(v.map_or(1, |v| v + 1))), None);
+        assert_eq!(map.compute("a", |v| Some(v.map_or(1, |v| v + 1))), Some(1));
+        assert_eq!(map.get(&"a"), Some(2));
+
+        // returning None removes
+        assert_eq!(map.compute("a", |_| None), Some(2));
+        assert!(!map.contains_key(&"a"));
+        assert_eq!(map.compute("b", |_| None), None);
+        assert!(map.is_empty());
+    }
+
+    #[test]
+    fn test_keys_spread_over_shards() {
+        let map = ShardedHashTable::new(8);
+        for key in 0..800 {
+            map.insert(key, ());
+        }
+        for shard in &map.shards {
+            let len = shard.read().unwrap().len();
+            assert!((50..150).contains(&len), "{}", len);
+        }
+        map.clear();
+        assert!(map.is_empty());
+    }
+
+    #[test]
+    fn test_shard_follows_table_hash() {
+        let map = ShardedHashTable::new(8);
+        for key in 0..100 {
+            map.insert(key, ());
+            let hash = lock_read(&map.shards[0]).hash(&key);
+            let index = hash.rotate_right(usize::BITS / 2) % 8;
+            assert!(lock_read(&map.shards[index]).contains_key(&key));
+        }
+    }
+
+    #[test]
+    fn test_survives_panicking_compute() {
+        let map = ShardedHashTable::new(1);
+        map.insert(1, 1);
+        let result = thread::scope(|s| {
+            s.spawn(|| map.compute(1, |_| panic!("callback failed")))
+                .join()
+        });
+        assert!(result.is_err());
+        assert_eq!(map.get(&1), Some(1));
+    }
+
+    #[test]
+    #[should_panic(expected = "shard count must be greater than zero")]
+    fn test_zero_shards() {
+        ShardedHashTable::<i32, i32>::new(0);
+    }
+
+    // Threads own disjoint keys but share every shard, so each thread must
+    // see exactly what a reference map shows for its keys.
+    #[test]
+    fn test_disjoint_keys_match_reference() {
+        let map = ShardedHashTable::new(4);
+        let reference = Mutex::new(HashMap::new());
+
+        thread::scope(|s| {
+            for id in 0..THREADS {
+                let (map, reference) = (&map, &reference);
+                s.spawn(move || {
+                    let mut rng = Rng(0x51ed_0000 + id as u64);
+                    for step in 0..2000 {
+                        let key = (rng.below(64) * THREADS + id) as u32;
+                        match rng.below(4) {
+                            0 => assert_eq!(
+                                map.insert(key, step),
+                                reference.lock().unwrap().insert(key, step)
+                            ),
+                            1 => {
+                                assert_eq!(map.remove(&key), reference.lock().unwrap().remove(&key))
+                            }
+                            2 => {
+                                let old = map.compute(key, |v| Some(v.map_or(0, |v| v + 1)));
+                                let mut reference = reference.lock().unwrap();
+                                let expected = reference.get(&key).copied();
+                                reference.insert(key, expected.map_or(0, |v| v + 1));
+                                assert_eq!(old, expected);
+                            }
+                            _ => assert_eq!(
+                                map.get(&key),
+                                reference.lock().unwrap().get(&key).copied()
+                            ),
+                        }
+                    }
+                });
+            }
+        });
+
+        let reference = reference.into_inner().unwrap();
+        assert_eq!(map.len(), reference.len());
+        for (key, value) in &reference {
+            assert_eq!(map.get(key), Some(*value));
+        }
+    }
+
+    // Every thread increments the same few counters; no update may be lost.
+    #[test]
+    fn test_concurrent_compute_loses_no_updates() {
+        let map = ShardedHashTable::new(4);
+        let reference = Mutex::new(HashMap::new());
+
+        thread::scope(|s| {
+            for id in 0..THREADS {
+                let (map, reference) = (&map, &reference);
+                s.spawn(move || {
+                    let mut rng = Rng(0xc0de_0000 + id as u64);
+                    for _ in 0..5000 {
+                        let key = rng.below(16);
+                        map.compute(key, |v| Some(v.map_or(1, |v| v + 1)));
+                        *reference.lock().unwrap().entry(key).or_insert(0) += 1;
+                    }
+                });
+            }
+        });
+
+        let reference = reference.into_inner().unwrap();
+        assert_eq!(reference.values().sum::<usize>(), THREADS * 5000);
+        for (key, count) in &reference {
+            assert_eq!(map.get(key), Some(*count));
+        }
+    }
+
+    // Threads race to insert and remove the same keys. Each call either
+    // added a key (an insert returning `None`) or took one away (a remove
+    // returning `Some`), so per key the adds minus the takes must equal
+    // whether it is present at the end.
+    #[test]
+    fn test_racing_insert_remove_balance() {
+        let map = ShardedHashTable::new(2);
+        let balance = Mutex::new(HashMap::<usize, i64>::new());
+
+        thread::scope(|s| {
+            for id in 0..THREADS {
+                let (map, balance) = (&map, &balance);
+                s.spawn(move || {
+                    let mut rng = Rng(0xba1a_0000 + id as u64);
+                    let mut local = HashMap::new();
+                    for _ in 0..5000 {
+                        let key = rng.below(8);
+                        let delta = if rng.below(2) == 0 {
+                            i64::from(map.insert(key, id).is_none())
+                        } else {
+                            -i64::from(map.remove(&key).is_some())
+                        };
+                        *local.entry(key).or_insert(0) += delta;
+                    }
+
+                    let mut balance = balance.lock().unwrap();
+                    for (key, delta) in local {
+                        *balance.entry(key).or_insert(0) += delta;
+                    }
+                });
+            }
+        });
+
+        let balance = balance.into_inner().unwrap();
+        for key in 0..8 {
+            let expected = balance.get(&key).copied().unwrap_or(0);
+            assert_eq!(i64::from(map.contains_key(&key)), expected, "key {}", key);
+        }
+    }
+}
@@ -0,0 +1,154 @@
+use std::hash::Hash;
+
+use crate::HashTable;
+
+/// A snapshot of how a [`HashTable`]'s entries spread over its buckets,
+/// taken by [`HashTable::stats`].
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct TableStats {
+    pub len: usize,
+    /// Buckets walked: the current ones plus, while an incremental rehash
+    /// runs, the old ones not yet moved.
+    pub buckets: usize,
+    /// Entries per walked bucket.
+    pub load_factor: f64,
+    pub longest_chain: usize,
+    /// `chain_lengths[n]` is the number of buckets holding `n` entries.
+    pub chain_lengths: Vec<usize>,
+    pub empty_buckets: usize,
+    /// Times the table has grown or shrunk since it was created.
+    pub resizes: usize,
+    /// Entries compared, on average, to find a key that is present. 1.0 is
+    /// ideal; values near `len / 2` mean the keys collide.
+    pub average_probes: f64,
+}
+
+impl<K, V, S> HashTable<K, V, S>
+where
+    K: Hash + PartialEq,
+{
+    /// Walks every bucket, so it costs O(capacity). While an incremental
+    /// rehash runs, the old buckets not yet moved are counted as well.
+    pub fn stats(&self) -> TableStats {
+        let unmigrated = &self.old_buckets[self.migrated..];
+        let buckets = unmigrated.len() + self.buckets.len();
+        let chains = unmigrated.iter().chain(&self.buckets).map(|el| el.len());
+
+        let mut chain_lengths = vec![0];
+        // finding the i-th entry of a chain takes i comparisons
+        let mut probes = 0;
+        for len in chains {
+            if chain_lengths.len() <= len {
+                chain_lengths.resize(len + 1, 0);
+            }
+            chain_lengths[len] += 1;
+            probes += len * (len + 1) / 2;
+        }
+
+        TableStats {
+            len: self.size,
+            buckets,
+            load_factor: self.size as f64 / buckets as f64,
+            longest_chain: chain_lengths.len() - 1,
+            empty_buckets: chain_lengths[0],
+            chain_lengths,
+            resizes: self.resizes,
+            average_probes: if self.size == 0 {
+                0.0
+            } else {
+                probes as f64 / self.size as f64
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::hash::{BuildHasherDefault, Hasher};
+
+    // Sends every key to the same bucket.
+    #[derive(Default)]
+    struct ConstantHasher;
+
+    impl Hasher for ConstantHasher {
+        fn write(&mut self, _bytes: &[u8]) {}
+
+        fn finish(&self) -> u64 {
+            0
+        }
+    }
+
+    #[test]
+    fn test_empty_table() {
+        let stats = HashTable::<i32, i32>::new(8).stats();
+        assert_eq!(stats.len, 0);
+        assert_eq!(stats.buckets, 8);
+        assert_eq!(stats.empty_buckets, 8);
+        assert_eq!(stats.chain_lengths, vec![8]);
+        assert_eq!(stats.longest_chain, 0);
+        assert_eq!(stats.average_probes, 0.0);
+    }
+
+    #[test]
+    fn test_histogram_accounts_for_every_bucket_and_entry() {
+        let mut ht = HashTable::new(4);
+        for key in 0..100 {
+            ht.insert(key, key);
+        }
+
+        let stats = ht.stats();
+        assert_eq!(stats.chain_lengths.iter().sum::<usize>(), stats.buckets);
+        let entries: usize = stats
+            .chain_lengths
+            .iter()
+            .enumerate()
+            .map(|(len, count)| len * count)
+            .sum();
+        assert_eq!(entries, 100);
+        assert_eq!(stats.load_factor, 100.0 / stats.buckets as f64);
+        assert!(stats.average_probes >= 1.0);
+        // 4 doubled to 256
+        assert_eq!(stats.resizes, 6);
+    }
+
+    #[test]
+    fn test_detects_a_degenerate_hasher() {
+        let mut ht = HashTable::with_capacity_and_hasher(
+            16,
+            BuildHasherDefault::<ConstantHasher>::default(),
+        )
+        .load_factors(100.0, 0.0);
+        for key in 0..10 {
+            ht.insert(key, ());
+        }
+
+        let stats = ht.stats();
+        assert_eq!(stats.longest_chain, 10);
+        assert_eq!(stats.empty_buckets, 15);
+        assert_eq!(stats.average_probes, 5.5);
+        assert_eq!(stats.resizes, 0);
+    }
+
+    #[test]
+    fn test_counts_old_buckets_during_incremental_rehash() {
+        let mut ht = HashTable::new(4).incremental_rehash(true);
+        // the 13th entry starts moving 16 buckets into 32
+        for key in 0..13 {
+            ht.insert(key, key);
+        }
+        assert!(ht.is_rehashing());
+
+        let stats = ht.stats();
+        let entries: usize = stats
+            .chain_lengths
+            .iter()
+            .enumerate()
+            .map(|(len, count)| len * count)
+            .sum();
+        assert_eq!(entries, 13);
+        assert_eq!(stats.chain_lengths.iter().sum::<usize>(), stats.buckets);
+        assert_eq!(stats.buckets, 32 + 16 - ht.migrated);
+        assert_eq!(stats.load_factor, 13.0 / stats.buckets as f64);
+    }
+}
@@ -0,0 +1,457 @@
+//! Classic list algorithms. Anything that reorders the list relinks the
+//! existing nodes instead of allocating new ones.
+
+use std::{cmp::Ordering, mem};
+
+use crate::LinkedList;
+
+impl<T> LinkedList<T> {
+    /// Reverses every consecutive group of `k` elements. A trailing group
+    /// shorter than `k` keeps its order. `k <= 1` leaves the list unchanged.
+    pub fn reverse_k_groups(&mut self, k: usize) {
+        if k <= 1 {
+            return;
+        }
+
+        let mut rest = mem::take(self);
+        while rest.len() >= k {
+            let tail = rest.split_off(k);
+            rest.reverse();
+            self.append(&mut rest);
+            rest = tail;
+        }
+        self.append(&mut rest);
+    }
+
+    /// Middle element found with a slow and a fast cursor. For an even length
+    /// this is the second of the two middle elements.
+    pub fn middle(&self) -> Option<&T> {
+        let mut slow = self.iter();
+        let mut fast = self.iter();
+
+        let mut middle = slow.next();
+        while fast.next().is_some() && fast.next().is_some() {
+            middle = slow.next();
+        }
+        middle
+    }
+
+    /// Removes the `n`-th element counting from the back, where `n = 1` is
+    /// the last element. Returns `None` if `n` is 0 or larger than the list.
+    pub fn remove_nth_from_end(&mut self, n: usize) -> Option<T> {
+        if n == 0 || n > self.size {
+            return None;
+        }
+        self.remove(self.size - n)
+    }
+
+    pub fn is_palindrome(&self) -> bool
+    where
+        T: PartialEq,
+    {
+        let half = self.size / 2;
+        self.iter().take(half).eq(self.iter().rev().take(half))
+    }
+
+    /// Stable merge sort.
+    pub fn sort(&mut self)
+    where
+        T: Ord,
+    {
+        self.sort_by(T::cmp);
+    }
+
+    /// Stable merge sort with a custom comparator.
+    pub fn sort_by<F>(&mut self, mut compare: F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        let list = mem::take(self);
+        *self = merge_sort(list, &mut compare);
+    }
+}
+
+fn merge_sort<T, F>(mut list: LinkedList<T>, compare: &mut F) -> LinkedList<T>
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    if list.len() <= 1 {
+        return list;
+    }
+
+    let right = list.split_off(list.len() / 2);
+    let left = merge_sort(list, compare);
+    let right = merge_sort(right, compare);
+    merge_by(left, right, compare)
+}
+
+// Ties are taken from `a` first, which keeps the merge stable.
+fn merge_by<T, F>(mut a: LinkedList<T>, mut b: LinkedList<T>, compare: &mut F) -> LinkedList<T>
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    let mut merged = LinkedList::new();
+    while let (Some(x), Some(y)) = (a.peek_front(), b.peek_front()) {
+        let from = if compare(y, x) == Ordering::Less {
+            &mut b
+        } else {
+            &mut a
+        };
+        if let Some(node) = from.pop_front_node() {
+            merged.push_back_node(node);
+        }
+    }
+
+    merged.append(&mut a);
+    merged.append(&mut b);
+    merged
+}
+
+/// Merges two sorted lists into one sorted list. Equal elements from `a`
+/// come before those from `b`.
+pub fn merge_sorted<T>(a: LinkedList<T>, b: LinkedList<T>) -> LinkedList<T>
+where
+    T: Ord,
+{
+    merge_by(a, b, &mut T::cmp)
+}
+
+/// Merges `k` sorted lists by merging them pairwise, in O(n log k). Equal
+/// elements keep the order of the lists they came from.
+pub fn merge_k_sorted<T>(mut lists: Vec<LinkedList<T>>) -> LinkedList<T>
+where
+    T: Ord,
+{
+    while lists.len() > 1 {
+        let mut merged = Vec::with_capacity(lists.len().div_ceil(2));
+        let mut pairs = lists.into_iter();
+        while let Some(a) = pairs.next() {
+            match pairs.next() {
+                Some(b) => merged.push(merge_sorted(a, b)),
+                None => merged.push(a),
+            }
+        }
+        lists = merged;
+    }
+
+    lists.pop().unwrap_or_default()
+}
+
+/// Floyd's cycle detection on a singly linked list stored as successor
+/// indices: `next[i]` is the node after `i`, or `None` at the end.
+///
+/// [`LinkedList`] owns its nodes and can never form a cycle, so cycles are
+/// only possible in index-based lists like this one.
+pub fn has_cycle(next: &[Option<usize>], head: Option<usize>) -> bool {
+    meeting_point(next, head).is_some()
+}
+
+/// Index of the first node on the cycle reachable from `head`, if any.
+pub fn cycle_start(next: &[Option<usize>], head: Option<usize>) -> Option<usize> {
+    let mut meet = meeting_point(next, head)?;
+
+    // the distance from head to the cycle start equals the distance from the
+    // meeting point to the cycle start, going round the cycle
+    let mut cur = head?;
+    while cur != meet {
+        cur = next[cur]?;
+        meet = next[meet]?;
+    }
+    Some(cur)
+}
+
+// Node where the slow and fast cursors meet, or `None` if fast hits the end.
+fn meeting_point(next: &[Option<usize>], head: Option<usize>) -> Option<usize> {
+    let mut slow = head?;
+    let mut fast = head?;
+    loop {
+        slow = next[slow]?;
+        fast = next[next[fast]?]?;
+        if slow == fast {
+            return Some(slow);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use test_util::Rng;
+
+    fn list(values: &[i32]) -> LinkedList<i32> {
+        values.iter().copied().collect()
+    }
+
+    fn values(list: &LinkedList<i32>) -> Vec<i32> {
+        list.iter().copied().collect()
+    }
+
+    #[test]
+    fn test_reverse_k_groups() {
+        let mut l = list(&[1, 2, 3, 4, 5, 6, 7, 8]);
+        l.reverse_k_groups(3);
+        assert_eq!(values(&l), vec![3, 2, 1, 6, 5, 4, 7, 8]);
+        assert_eq!(l.len(), 8);
+
+        let mut l = list(&[1, 2, 3]);
+        l.reverse_k_groups(0);
+        assert_eq!(values(&l), vec![1, 2, 3]);
+        l.reverse_k_groups(4);
+        assert_eq!(values(&l), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_reverse_k_groups_matches_vec() {
+        let mut rng = Rng(0x1234_5678);
+        for _ in 0..200 {
+            let v = rng.vec(30, 100);
+            let k = rng.below(8);
+
+            let mut expected = Vec::new();
+            for chunk in v.chunks(k.max(1)) {
+                if k > 1 && chunk.len() == k {
+                    expected.extend(chunk.iter().rev());
+                } else {
+                    expected.extend(chunk);
+                }
+            }
+
+            let mut l = list(&v);
+            l.reverse_k_groups(k);
+            assert_eq!(values(&l), expected);
+            assert_eq!(l.peek_back(), expected.last());
+        }
+    }
+
+    #[test]
+    fn test_middle() {
+        assert_eq!(list(&[]).middle(), None);
+        assert_eq!(list(&[1]).middle(), Some(&1));
+        assert_eq!(list(&[1, 2]).middle(), Some(&2));
+        assert_eq!(list(&[1, 2, 3, 4, 5]).middle(), Some(&3));
+
+        let mut rng = Rng(42);
+        for _ in 0..100 {
+            let v = rng.vec(20, 100);
+            assert_eq!(list(&v).middle(), v.get(v.len() / 2));
+        }
+    }
+
+    #[test]
+    fn test_remove_nth_from_end() {
+        let mut l = list(&[1, 2, 3, 4, 5]);
+        assert_eq!(l.remove_nth_from_end(2), Some(4));
+        assert_eq!(l.remove_nth_from_end(4), Some(1));
+        assert_eq!(l.remove_nth_from_end(0), None);
+        assert_eq!(l.remove_nth_from_end(4), None);
+        assert_eq!(values(&l), vec![2, 3, 5]);
+
+        let mut rng = Rng(7);
+        for _ in 0..100 {
+            let mut v = rng.vec(20, 100);
+            let n = rng.below(v.len() + 2);
+
+            let mut l = list(&v);
+            let expected = (1..=v.len()).contains(&n).then(|| v.remove(v.len() - n));
+            assert_eq!(l.remove_nth_from_end(n), expected);
+            assert_eq!(values(&l), v);
+        }
+    }
+
+    #[test]
+    fn test_is_palindrome() {
+        assert!(list(&[]).is_palindrome());
+        assert!(list(&[1]).is_palindrome());
+        assert!(list(&[1, 2, 2, 1]).is_palindrome());
+        assert!(list(&[1, 2, 3, 2, 1]).is_palindrome());
+        assert!(!list(&[1, 2, 3, 1]).is_palindrome());
+
+        let mut rng = Rng(99);
+        for _ in 0..200 {
+            // small alphabet so palindromes actually show up
+            let v = rng.vec(6, 2);
+            let reversed: Vec<i32> = v.iter().rev().copied().collect();
+            assert_eq!(list(&v).is_palindrome(), v == reversed);
+        }
+    }
+
+    #[test]
+    fn test_sort_matches_vec() {
+        let mut rng = Rng(0xdead_beef);
+        for _ in 0..200 {
+            let mut v = rng.vec(50, 20);
+            let mut l = list(&v);
+
+            l.sort();
+            v.sort();
+            assert_eq!(values(&l), v);
+            assert_eq!(l.len(), v.len());
+            assert_eq!(l.peek_back(), v.last());
+        }
+    }
+
+    #[test]
+    fn test_sort_by_descending() {
+        let mut l = list(&[3, 1, 4, 1, 5, 9, 2, 6]);
+        l.sort_by(|a, b| b.cmp(a));
+        assert_eq!(values(&l), vec![9, 6, 5, 4, 3, 2, 1, 1]);
+    }
+
+    // Ordered by `key` only, so `tag` shows whether equal keys kept their order.
+    #[derive(Debug, Clone, Copy)]
+    struct Keyed {
+        key: i32,
+        tag: usize,
+    }
+
+    impl PartialEq for Keyed {
+        fn eq(&self, other: &Self) -> bool {
+            self.key == other.key
+        }
+    }
+
+    impl Eq for Keyed {}
+
+    impl PartialOrd for Keyed {
+        fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+            Some(self.cmp(other))
+        }
+    }
+
+    impl Ord for Keyed {
+        fn cmp(&self, other: &Self) -> Ordering {
+            self.key.cmp(&other.key)
+        }
+    }
+
+    fn keyed(keys: &[i32]) -> Vec<Keyed> {
+        keys.iter()
+            .enumerate()
+            .map(|(tag, &key)| Keyed { key, tag })
+            .collect()
+    }
+
+    fn tags<'a>(values: impl Iterator<Item = &'a Keyed>) -> Vec<usize> {
+        values.map(|value| value.tag).collect()
+    }
+
+    #[test]
+    fn test_sort_is_stable() {
+        let mut rng = Rng(5);
+        for _ in 0..100 {
+            let mut v = keyed(&rng.vec(40, 5));
+            let mut l: LinkedList<Keyed> = v.iter().copied().collect();
+
+            l.sort();
+            // `slice::sort` is stable
+            v.sort();
+            assert_eq!(tags(l.iter()), tags(v.iter()));
+        }
+    }
+
+    #[test]
+    fn test_merge_sorted() {
+        let merged = merge_sorted(list(&[1, 3, 5]), list(&[2, 3, 4, 6, 7]));
+        assert_eq!(values(&merged), vec![1, 2, 3, 3, 4, 5, 6, 7]);
+
+        let merged = merge_sorted(list(&[]), list(&[1]));
+        assert_eq!(values(&merged), vec![1]);
+    }
+
+    #[test]
+    fn test_merge_sorted_matches_vec() {
+        let mut rng = Rng(11);
+        for _ in 0..200 {
+            let mut a = rng.vec(20, 30);
+            let mut b = rng.vec(20, 30);
+            a.sort();
+            b.sort();
+
+            let merged = merge_sorted(list(&a), list(&b));
+            let mut expected = [a, b].concat();
+            expected.sort();
+            assert_eq!(values(&merged), expected);
+        }
+    }
+
+    #[test]
+    fn test_merge_sorted_takes_ties_from_first() {
+        let all = keyed(&[1, 2, 1, 2]);
+        let a: LinkedList<Keyed> = [all[0], all[1]].into_iter().collect();
+        let b: LinkedList<Keyed> = [all[2], all[3]].into_iter().collect();
+
+        assert_eq!(tags(merge_sorted(a, b).iter()), vec![0, 2, 1, 3]);
+    }
+
+    #[test]
+    fn test_merge_k_sorted() {
+        assert!(merge_k_sorted::<i32>(Vec::new()).is_empty());
+
+        let lists = vec![list(&[1, 4, 5]), list(&[1, 3, 4]), list(&[]), list(&[2, 6])];
+        assert_eq!(values(&merge_k_sorted(lists)), vec![1, 1, 2, 3, 4, 4, 5, 6]);
+
+        let mut rng = Rng(13);
+        for _ in 0..100 {
+            let mut all = Vec::new();
+            let mut lists = Vec::new();
+            for _ in 0..rng.below(8) {
+                let mut v = rng.vec(10, 50);
+                v.sort();
+                all.extend(&v);
+                lists.push(list(&v));
+            }
+
+            all.sort();
+            assert_eq!(values(&merge_k_sorted(lists)), all);
+        }
+    }
+
+    #[test]
+    fn test_cycle_detection() {
+        // 0 -> 1 -> 2 -> 3 -> 4 -> 2
+        let next = [Some(1), Some(2), Some(3), Some(4), Some(2)];
+        assert!(has_cycle(&next, Some(0)));
+        assert_eq!(cycle_start(&next, Some(0)), Some(2));
+        assert_eq!(cycle_start(&next, Some(3)), Some(3));
+
+        // 0 -> 1 -> 2
+        let next = [Some(1), Some(2), None];
+        assert!(!has_cycle(&next, Some(0)));
+        assert_eq!(cycle_start(&next, Some(0)), None);
+        assert_eq!(cycle_start(&next, None), None);
+
+        // self-loop
+        assert_eq!(cycle_start(&[Some(0)], Some(0)), Some(0));
+    }
+
+    #[test]
+    fn test_cycle_start_matches_naive() {
+        let mut rng = Rng(17);
+        for _ in 0..200 {
+            let n = rng.below(12) + 1;
+            let next: Vec<Option<usize>> = (0..n)
+                .map(|_| {
+                    let to = rng.below(n + 2);
+                    (to < n).then_some(to)
+                })
+                .collect();
+            let head = rng.below(n);
+
+            // first node visited twice while following the chain
+            let mut seen = vec![false; n];
+            let mut cur = Some(head);
+            let mut expected = None;
+            while let Some(node) = cur {
+                if seen[node] {
+                    expected = Some(node);
+                    break;
+                }
+                seen[node] = true;
+                cur = next[node];
+            }
+
+            assert_eq!(cycle_start(&next, Some(head)), expected);
+            assert_eq!(has_cycle(&next, Some(head)), expected.is_some());
+        }
+    }
+}
@@ -0,0 +1,398 @@
+use std::{mem, ptr::NonNull};
+
+use crate::{LinkedList, Node};
+
+/// A cursor that can move over a [`LinkedList`] and edit it in O(1) at its
+/// position.
+///
+/// Like the unstable std cursor, it sits either on an element or on a
+/// "ghost" position between the tail and the head. Moving past either end
+/// lands on the ghost, and moving once more wraps around to the other end.
+pub struct CursorMut<'a, T> {
+    // equals `list.len()` on the ghost
+    index: usize,
+    current: Option<NonNull<Node<T>>>,
+    list: &'a mut LinkedList<T>,
+}
+
+impl<T> LinkedList<T> {
+    /// Cursor on the first element, or on the ghost if the list is empty.
+    pub fn cursor_front_mut(&mut self) -> CursorMut<'_, T> {
+        CursorMut {
+            index: 0,
+            current: self.head,
+            list: self,
+        }
+    }
+
+    /// Cursor on the last element, or on the ghost if the list is empty.
+    pub fn cursor_back_mut(&mut self) -> CursorMut<'_, T> {
+        CursorMut {
+            index: self.size.saturating_sub(1),
+            current: self.tail,
+            list: self,
+        }
+    }
+}
+
+impl<T> CursorMut<'_, T> {
+    /// Index of the current element, or `None` on the ghost.
+    pub fn index(&self) -> Option<usize> {
+        self.current.map(|_| self.index)
+    }
+
+    pub fn move_next(&mut self) {
+        match self.current {
+            None => {
+                self.current = self.list.head;
+                self.index = 0;
+            }
+            Some(node) => {
+                // SAFETY: `current` is a live node of the borrowed list.
+                self.current = unsafe { (*node.as_ptr()).next };
+                self.index += 1;
+            }
+        }
+    }
+
+    pub fn move_prev(&mut self) {
+        match self.current {
+            None => {
+                self.current = self.list.tail;
+                self.index = self.list.size.saturating_sub(1);
+            }
+            Some(node) => {
+                // SAFETY: `current` is a live node of the borrowed list.
+                self.current = unsafe { (*node.as_ptr()).prev };
+                self.index = self.index.checked_sub(1).unwrap_or(self.list.size);
+            }
+        }
+    }
+
+    /// The element under the cursor, or `None` on the ghost.
+    pub fn current(&mut self) -> Option<&mut T> {
+        // SAFETY: the cursor borrows the list mutably, so this is the only
+        // reference into the node.
+        self.current
+            .map(|node| unsafe { &mut (*node.as_ptr()).value })
+    }
+
+    /// The element `move_next` would land on.
+    pub fn peek_next(&mut self) -> Option<&mut T> {
+        let next = self.next_node();
+        // SAFETY: see `current`.
+        next.map(|node| unsafe { &mut (*node.as_ptr()).value })
+    }
+
+    /// The element `move_prev` would land on.
+    pub fn peek_prev(&mut self) -> Option<&mut T> {
+        let prev = self.prev_node();
+        // SAFETY: see `current`.
+        prev.map(|node| unsafe { &mut (*node.as_ptr()).value })
+    }
+
+    /// Inserts `value` before the cursor. On the ghost this appends to the
+    /// back.
+    pub fn insert_before(&mut self, value: T) {
+        let node = NonNull::from(Box::leak(Box::new(Node::new(value))));
+        let prev = self.prev_node();
+        // SAFETY: `prev` and `current` are adjacent nodes of the list and
+        // `node` was just allocated.
+        unsafe { self.list.link_between(prev, self.current, node, node, 1) };
+        self.index += 1;
+    }
+
+    /// Inserts `value` after the cursor. On the ghost this pushes to the
+    /// front.
+    pub fn insert_after(&mut self, value: T) {
+        let node = NonNull::from(Box::leak(Box::new(Node::new(value))));
+        let next = self.next_node();
+        // SAFETY: see `insert_before`.
+        unsafe { self.list.link_between(self.current, next, node, node, 1) };
+        if self.current.is_none() {
+            self.index += 1;
+        }
+    }
+
+    /// Removes the current element and moves the cursor to the next one.
+    /// Does nothing on the ghost.
+    pub fn remove_current(&mut self) -> Option<T> {
+        let node = self.current?;
+        // SAFETY: `current` is a live node of the borrowed list.
+        unsafe {
+            self.current = (*node.as_ptr()).next;
+            Some(self.list.unlink(node))
+        }
+    }
+
+    /// Moves every element of `other` in after the cursor in O(1). On the
+    /// ghost they go to the front of the list.
+    pub fn splice_after(&mut self, mut other: LinkedList<T>) {
+        let (first, last) = match (other.head.take(), other.tail.take()) {
+            (Some(first), Some(last)) => (first, last),
+            _ => return,
+        };
+        let count = mem::replace(&mut other.size, 0);
+
+        let next = self.next_node();
+        // SAFETY: `other` gave up its nodes above, so they now belong to
+        // this list; `current` and `next` are adjacent.
+        unsafe {
+            self.list
+                .link_between(self.current, next, first, last, count)
+        };
+        if self.current.is_none() {
+            self.index += count;
+        }
+    }
+
+    /// Splits off everything after the cursor into a new list in O(1). On the
+    /// ghost the whole list is moved out.
+    pub fn split_after(&mut self) -> LinkedList<T> {
+        let node = match self.current {
+            Some(node) => node,
+            None => {
+                self.index = 0;
+                return mem::take(self.list);
+            }
+        };
+
+        // SAFETY: `node` and its successor are live nodes of the list;
+        // ownership of everything after `node` moves to the new list.
+        unsafe {
+            let first = match (*node.as_ptr()).next.take() {
+                Some(first) => first,
+                None => return LinkedList::new(),
+            };
+            (*first.as_ptr()).prev = None;
+
+            let kept = self.index + 1;
+            let mut other = LinkedList::new();
+            other.head = Some(first);
+            other.tail = self.list.tail.replace(node);
+            other.size = self.list.size - kept;
+            self.list.size = kept;
+            other
+        }
+    }
+
+    fn next_node(&self) -> Option<NonNull<Node<T>>> {
+        match self.current {
+            // SAFETY: `current` is a live node of the borrowed list.
+            Some(node) => unsafe { (*node.as_ptr()).next },
+            None => self.list.head,
+        }
+    }
+
+    fn prev_node(&self) -> Option<NonNull<Node<T>>> {
+        match self.current {
+            // SAFETY: `current` is a live node of the borrowed list.
+            Some(node) => unsafe { (*node.as_ptr()).prev },
+            None => self.list.tail,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use test_util::Rng;
+
+    // Checks the backward links agree with the forward ones.
+    fn values(list: &LinkedList<i32>) -> Vec<i32> {
+        let forward: Vec<i32> = list.iter().copied().collect();
+        let mut backward: Vec<i32> = list.iter().rev().copied().collect();
+        backward.reverse();
+        assert_eq!(forward, backward);
+        assert_eq!(forward.len(), list.len());
+        forward
+    }
+
+    #[test]
+    fn test_move_and_wrap_around() {
+        let mut list: LinkedList<i32> = (1..=3).collect();
+        let mut cursor = list.cursor_front_mut();
+
+        assert_eq!(cursor.index(), Some(0));
+        assert_eq!(cursor.current(), Some(&mut 1));
+        cursor.move_next();
+        cursor.move_next();
+        assert_eq!(cursor.current(), Some(&mut 3));
+        assert_eq!(cursor.peek_next(), None);
+
+        // onto the ghost, then around to the head
+        cursor.move_next();
+        assert_eq!(cursor.index(), None);
+        assert_eq!(cursor.current(), None);
+        assert_eq!(cursor.peek_next(), Some(&mut 1));
+        assert_eq!(cursor.peek_prev(), Some(&mut 3));
+        cursor.move_next();
+        assert_eq!(cursor.index(), Some(0));
+
+        cursor.move_prev();
+        cursor.move_prev();
+        assert_eq!(cursor.index(), Some(2));
+        assert_eq!(cursor.current(), Some(&mut 3));
+    }
+
+    #[test]
+    fn test_cursor_on_empty_list() {
+        let mut list = LinkedList::new();
+        let mut cursor = list.cursor_back_mut();
+        assert_eq!(cursor.index(), None);
+        cursor.move_next();
+        cursor.move_prev();
+        assert_eq!(cursor.remove_current(), None);
+
+        cursor.insert_after(2);
+        cursor.insert_before(3);
+        cursor.insert_after(1);
+        assert_eq!(values(&list), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_edit_in_the_middle() {
+        let mut list: LinkedList<i32> = (1..=5).collect();
+        let mut cursor = list.cursor_front_mut();
+        cursor.move_next();
+        cursor.move_next();
+
+        *cursor.current().unwrap() = 30;
+        cursor.insert_before(25);
+        cursor.insert_after(35);
+        assert_eq!(cursor.index(), Some(3));
+
+        assert_eq!(cursor.remove_current(), Some(30));
+        assert_eq!(cursor.current(), Some(&mut 35));
+        assert_eq!(cursor.index(), Some(3));
+        assert_eq!(values(&list), vec![1, 2, 25, 35, 4, 5]);
+    }
+
+    #[test]
+    fn test_remove_last_moves_to_ghost() {
+        let mut list: LinkedList<i32> = (1..=2).collect();
+        let mut cursor = list.cursor_back_mut();
+        assert_eq!(cursor.remove_current(), Some(2));
+        assert_eq!(cursor.index(), None);
+        cursor.move_prev();
+        assert_eq!(cursor.current(), Some(&mut 1));
+        assert_eq!(values(&list), vec![1]);
+    }
+
+    #[test]
+    fn test_splice_after() {
+        let mut list: LinkedList<i32> = [1, 5].into_iter().collect();
+        let mut cursor = list.cursor_front_mut();
+        cursor.splice_after((2..=4).collect());
+        cursor.splice_after(LinkedList::new());
+        assert_eq!(cursor.index(), Some(0));
+        cursor.move_next();
+        assert_eq!(cursor.current(), Some(&mut 2));
+
+        let mut cursor = list.cursor_back_mut();
+        cursor.move_next();
+        cursor.splice_after([-1, 0].into_iter().collect());
+        assert_eq!(values(&list), vec![-1, 0, 1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn test_split_after() {
+        let mut list: LinkedList<i32> = (1..=5).collect();
+        let mut cursor = list.cursor_front_mut();
+        cursor.move_next();
+
+        let tail = cursor.split_after();
+        assert_eq!(cursor.index(), Some(1));
+        assert!(cursor.split_after().is_empty());
+        assert_eq!(values(&tail), vec![3, 4, 5]);
+        assert_eq!(values(&list), vec![1, 2]);
+
+        let mut cursor = list.cursor_back_mut();
+        cursor.move_next();
+        let all = cursor.split_after();
+        assert_eq!(values(&all), vec![1, 2]);
+        assert!(list.is_empty());
+    }
+
+    #[test]
+    fn test_move_to_front() {
+        // the LRU "touch" operation: find an element and move it to the front
+        let mut list: LinkedList<i32> = (1..=5).collect();
+        let mut cursor = list.cursor_front_mut();
+        while cursor.current() != Some(&mut 4) {
+            cursor.move_next();
+        }
+
+        let value = cursor.remove_current().unwrap();
+        list.push_front(value);
+        assert_eq!(values(&list), vec![4, 1, 2, 3, 5]);
+    }
+
+    #[test]
+    fn test_matches_vec_model() {
+        let mut rng = Rng(0x9e37_79b9_7f4a_7c15);
+        for _ in 0..100 {
+            let mut model: Vec<i32> = rng.vec(8, 100);
+            let mut list: LinkedList<i32> = model.iter().copied().collect();
+            // `None` is the ghost
+            let mut pos = if model.is_empty() { None } else { Some(0) };
+            let mut cursor = list.cursor_front_mut();
+
+            for value in 0..60 {
+                match rng.below(8) {
+                    0 => {
+                        pos = match pos {
+                            None if model.is_empty() => None,
+                            None => Some(0),
+                            Some(i) if i + 1 == model.len() => None,
+                            Some(i) => Some(i + 1),
+                        };
+                        cursor.move_next();
+                    }
+                    1 => {
+                        pos = match pos {
+                            None => model.len().checked_sub(1),
+                            Some(i) => i.checked_sub(1),
+                        };
+                        cursor.move_prev();
+                    }
+                    2 => {
+                        let i = pos.unwrap_or(model.len());
+                        model.insert(i, value);
+                        pos = pos.map(|i| i + 1);
+                        cursor.insert_before(value);
+                    }
+                    3 => {
+                        model.insert(pos.map_or(0, |i| i + 1), value);
+                        cursor.insert_after(value);
+                    }
+                    4 => {
+                        let expected = pos.map(|i| model.remove(i));
+                        if pos == Some(model.len()) {
+                            pos = None;
+                        }
+                        assert_eq!(cursor.remove_current(), expected);
+                    }
+                    5 => {
+                        let other = rng.vec(3, 100);
+                        let at = pos.map_or(0, |i| i + 1);
+                        model.splice(at..at, other.iter().copied());
+                        cursor.splice_after(other.into_iter().collect());
+                    }
+                    6 => {
+                        let at = pos.map_or(0, |i| i + 1);
+                        let expected = model.split_off(at);
+                        assert_eq!(values(&cursor.split_after()), expected);
+                    }
+                    _ => {
+                        let expected = pos.map(|i| model[i]);
+                        assert_eq!(cursor.current().copied(), expected);
+                    }
+                }
+                assert_eq!(cursor.index(), pos);
+            }
+
+            assert_eq!(values(&list), model);
+        }
+    }
+}
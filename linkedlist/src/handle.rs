@@ -0,0 +1,171 @@
+use std::{fmt, ptr::NonNull};
+
+use crate::{LinkedList, Node};
+
+/// Points at one element of a [`LinkedList`] so it can be read, moved or
+/// removed in O(1) later, the way an LRU cache keeps its map entries pointing
+/// into the recency list.
+///
+/// A handle stays valid until its element is removed from the list. The
+/// list cannot check that, so every method taking a handle is `unsafe`.
+pub struct Handle<T>(NonNull<Node<T>>);
+
+// A handle is only an address; reading through it needs the list itself.
+unsafe impl<T> Send for Handle<T> {}
+unsafe impl<T> Sync for Handle<T> {}
+
+impl<T> Clone for Handle<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Handle<T> {}
+
+impl<T> PartialEq for Handle<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl<T> Eq for Handle<T> {}
+
+impl<T> fmt::Debug for Handle<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Handle").field(&self.0).finish()
+    }
+}
+
+/// # Safety
+/// Every `unsafe` method here requires `handle` to come from this list and
+/// its element to still be in it.
+impl<T> LinkedList<T> {
+    pub fn push_front_handle(&mut self, value: T) -> Handle<T> {
+        self.push_front(value);
+        Handle(self.head.expect("list is not empty"))
+    }
+
+    pub fn push_back_handle(&mut self, value: T) -> Handle<T> {
+        self.push_back(value);
+        Handle(self.tail.expect("list is not empty"))
+    }
+
+    pub fn front_handle(&self) -> Option<Handle<T>> {
+        self.head.map(Handle)
+    }
+
+    pub fn back_handle(&self) -> Option<Handle<T>> {
+        self.tail.map(Handle)
+    }
+
+    /// # Safety
+    /// See the `impl` block.
+    pub unsafe fn get_handle(&self, handle: Handle<T>) -> &T {
+        // SAFETY: the caller guarantees the node is live and in this list.
+        unsafe { &(*handle.0.as_ptr()).value }
+    }
+
+    /// # Safety
+    /// See the `impl` block.
+    pub unsafe fn get_handle_mut(&mut self, handle: Handle<T>) -> &mut T {
+        // SAFETY: as in `get_handle`; `&mut self` makes the access exclusive.
+        unsafe { &mut (*handle.0.as_ptr()).value }
+    }
+
+    /// Removes the element, invalidating `handle`.
+    ///
+    /// # Safety
+    /// See the `impl` block.
+    pub unsafe fn remove_handle(&mut self, handle: Handle<T>) -> T {
+        // SAFETY: forwarded from the caller.
+        unsafe { self.unlink(handle.0) }
+    }
+
+    /// Moves the element to the front. `handle` stays valid.
+    ///
+    /// # Safety
+    /// See the `impl` block.
+    pub unsafe fn move_to_front(&mut self, handle: Handle<T>) {
+        if self.head == Some(handle.0) {
+            return;
+        }
+        // SAFETY: the caller guarantees the node is in this list, so it can
+        // be detached and relinked without reallocating.
+        unsafe {
+            let node = NonNull::from(Box::leak(self.unlink_node(handle.0)));
+            self.link_between(None, self.head, node, node, 1);
+        }
+    }
+
+    /// Moves the element to the back. `handle` stays valid.
+    ///
+    /// # Safety
+    /// See the `impl` block.
+    pub unsafe fn move_to_back(&mut self, handle: Handle<T>) {
+        if self.tail == Some(handle.0) {
+            return;
+        }
+        // SAFETY: see `move_to_front`.
+        unsafe {
+            let node = NonNull::from(Box::leak(self.unlink_node(handle.0)));
+            self.link_between(self.tail, None, node, node, 1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn values(list: &LinkedList<i32>) -> Vec<i32> {
+        let forward: Vec<i32> = list.iter().copied().collect();
+        let mut backward: Vec<i32> = list.iter().rev().copied().collect();
+        backward.reverse();
+        assert_eq!(forward, backward);
+        forward
+    }
+
+    #[test]
+    fn test_handles_survive_other_edits() {
+        let mut list = LinkedList::new();
+        let one = list.push_back_handle(1);
+        let two = list.push_back_handle(2);
+        let zero = list.push_front_handle(0);
+        list.push_back(3);
+
+        assert_eq!(list.front_handle(), Some(zero));
+        assert_ne!(list.back_handle(), Some(two));
+
+        // SAFETY: none of the handles has been removed.
+        unsafe {
+            *list.get_handle_mut(two) = 20;
+            assert_eq!(list.remove_handle(one), 1);
+            assert_eq!(*list.get_handle(two), 20);
+        }
+        assert_eq!(values(&list), vec![0, 20, 3]);
+    }
+
+    #[test]
+    fn test_move_to_front_and_back() {
+        let mut list = LinkedList::new();
+        let handles: Vec<Handle<i32>> = (1..=4).map(|v| list.push_back_handle(v)).collect();
+
+        // SAFETY: every handle is still in the list.
+        unsafe {
+            list.move_to_front(handles[2]);
+            assert_eq!(values(&list), vec![3, 1, 2, 4]);
+            list.move_to_front(handles[2]);
+            assert_eq!(values(&list), vec![3, 1, 2, 4]);
+
+            list.move_to_back(handles[0]);
+            assert_eq!(values(&list), vec![3, 2, 4, 1]);
+            list.move_to_back(handles[3]);
+            list.move_to_front(handles[3]);
+            assert_eq!(values(&list), vec![4, 3, 2, 1]);
+
+            assert_eq!(*list.get_handle(handles[1]), 2);
+        }
+        assert_eq!(list.len(), 4);
+        assert_eq!(list.back_handle(), Some(handles[0]));
+    }
+}
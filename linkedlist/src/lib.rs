@@ -1,103 +1,468 @@
-#![allow(dead_code)]
+use std::{
+    fmt::{self, Debug, Display},
+    marker::PhantomData,
+    mem,
+    ptr::NonNull,
+};
 
-use std::{cell::RefCell, fmt::Debug, rc::Rc};
+pub mod algorithms;
+mod cursor;
+mod handle;
+mod iter;
 
-#[derive(Debug, Clone)]
-pub struct Node<T: Debug> {
-    pub next: Option<Rc<RefCell<Node<T>>>>,
-    pub value: T,
+pub use cursor::CursorMut;
+pub use handle::Handle;
+pub use iter::{IntoIter, Iter, IterMut};
+
+struct Node<T> {
+    value: T,
+    prev: Option<NonNull<Node<T>>>,
+    next: Option<NonNull<Node<T>>>,
 }
 
-impl<T> Node<T>
-where
-    T: Debug,
-{
-    pub fn new(value: T) -> Node<T> {
-        Node { value, next: None }
+impl<T> Node<T> {
+    fn new(value: T) -> Node<T> {
+        Node {
+            value,
+            prev: None,
+            next: None,
+        }
     }
 }
 
-#[derive(Debug, Clone)]
-pub struct LinkedList<T>
-where
-    T: Debug,
-{
-    pub head: Option<Rc<RefCell<Node<T>>>>,
-    pub tail: Option<Rc<RefCell<Node<T>>>>,
-    pub size: usize,
+/// A doubly linked list with O(1) push and pop at both ends.
+///
+/// Every node is owned by exactly one list; `prev`/`next` are raw pointers
+/// into boxes the list allocated itself, so nodes are never shared.
+pub struct LinkedList<T> {
+    head: Option<NonNull<Node<T>>>,
+    tail: Option<NonNull<Node<T>>>,
+    size: usize,
+    marker: PhantomData<Box<Node<T>>>,
 }
 
-impl<T> LinkedList<T>
-where
-    T: Debug,
-{
+// The list owns its nodes like a `Box` would.
+unsafe impl<T: Send> Send for LinkedList<T> {}
+unsafe impl<T: Sync> Sync for LinkedList<T> {}
+
+impl<T> Default for LinkedList<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> LinkedList<T> {
     pub fn new() -> LinkedList<T> {
         Self {
             head: None,
             tail: None,
             size: 0,
+            marker: PhantomData,
         }
     }
 
+    pub fn len(&self) -> usize {
+        self.size
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.size == 0
+    }
+
+    /// Same as [`LinkedList::push_back`].
     pub fn push(&mut self, value: T) {
-        let node = Rc::new(RefCell::new(Node::new(value)));
+        self.push_back(value);
+    }
+
+    pub fn push_back(&mut self, value: T) {
+        self.push_back_node(Box::new(Node::new(value)));
+    }
+
+    pub fn push_front(&mut self, value: T) {
+        let mut node = Box::new(Node::new(value));
+        node.next = self.head;
+        let node = NonNull::from(Box::leak(node));
+
+        match self.head {
+            // SAFETY: `head` points to a live node owned by this list.
+            Some(head) => unsafe { (*head.as_ptr()).prev = Some(node) },
+            None => self.tail = Some(node),
+        }
 
+        self.head = Some(node);
         self.size += 1;
-        if self.head.is_none() && self.tail.is_none() {
-            self.head = Some(node.clone());
-            self.tail = Some(node);
-        } else {
-            let tail = self.tail.as_ref().unwrap();
-            tail.borrow_mut().next = Some(node.clone());
-            self.tail = Some(node);
+    }
+
+    /// Same as [`LinkedList::pop_back`].
+    pub fn pop(&mut self) -> Option<T> {
+        self.pop_back()
+    }
+
+    pub fn pop_back(&mut self) -> Option<T> {
+        // SAFETY: `tail` is a live node owned by this list.
+        self.tail.map(|node| unsafe { self.unlink(node) })
+    }
+
+    pub fn pop_front(&mut self) -> Option<T> {
+        // SAFETY: `head` is a live node owned by this list.
+        self.head.map(|node| unsafe { self.unlink(node) })
+    }
+
+    pub fn peek_front(&self) -> Option<&T> {
+        // SAFETY: `head` is live for as long as `self` is borrowed.
+        self.head.map(|node| unsafe { &(*node.as_ptr()).value })
+    }
+
+    pub fn peek_back(&self) -> Option<&T> {
+        // SAFETY: `tail` is live for as long as `self` is borrowed.
+        self.tail.map(|node| unsafe { &(*node.as_ptr()).value })
+    }
+
+    pub fn peek_front_mut(&mut self) -> Option<&mut T> {
+        // SAFETY: `&mut self` guarantees exclusive access to the node.
+        self.head.map(|node| unsafe { &mut (*node.as_ptr()).value })
+    }
+
+    pub fn peek_back_mut(&mut self) -> Option<&mut T> {
+        // SAFETY: `&mut self` guarantees exclusive access to the node.
+        self.tail.map(|node| unsafe { &mut (*node.as_ptr()).value })
+    }
+
+    /// Returns the element at `index`, walking from whichever end is closer.
+    pub fn get(&self, index: usize) -> Option<&T> {
+        // SAFETY: the node is live for as long as `self` is borrowed.
+        self.node_at(index)
+            .map(|node| unsafe { &(*node.as_ptr()).value })
+    }
+
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        // SAFETY: `&mut self` guarantees exclusive access to the node.
+        self.node_at(index)
+            .map(|node| unsafe { &mut (*node.as_ptr()).value })
+    }
+
+    /// Inserts `value` so it ends up at `index`.
+    ///
+    /// # Panics
+    /// Panics if `index > len`.
+    pub fn insert(&mut self, index: usize, value: T) {
+        assert!(
+            index <= self.size,
+            "insertion index (is {index}) should be <= len (is {})",
+            self.size
+        );
+
+        if index == 0 {
+            return self.push_front(value);
+        }
+        if index == self.size {
+            return self.push_back(value);
         }
+
+        let next = self.node_at(index).expect("index is in range");
+        let node = NonNull::from(Box::leak(Box::new(Node::new(value))));
+        // SAFETY: `next` is a live node owned by this list, `prev` is its
+        // neighbour and `node` was just allocated.
+        unsafe {
+            let prev = (*next.as_ptr()).prev;
+            self.link_between(prev, Some(next), node, node, 1);
+        }
+    }
+
+    /// Removes and returns the element at `index`, or `None` if out of range.
+    pub fn remove(&mut self, index: usize) -> Option<T> {
+        let node = self.node_at(index)?;
+        // SAFETY: `node_at` only returns live nodes owned by this list.
+        Some(unsafe { self.unlink(node) })
     }
 
-    pub fn pop(&mut self) -> Option<T>
-    where
-        T: Clone,
-    {
-        if self.size == 0 {
+    /// Splits the list in two at `at`: `self` keeps `[0, at)` and the
+    /// returned list holds `[at, len)`.
+    ///
+    /// # Panics
+    /// Panics if `at > len`.
+    pub fn split_off(&mut self, at: usize) -> LinkedList<T> {
+        assert!(
+            at <= self.size,
+            "split index (is {at}) should be <= len (is {})",
+            self.size
+        );
+
+        if at == 0 {
+            return mem::take(self);
+        }
+        if at == self.size {
+            return LinkedList::new();
+        }
+
+        let first = self.node_at(at).expect("index is in range");
+        // SAFETY: `first` and its `prev` are live nodes owned by this list;
+        // `at > 0` so `prev` exists. Ownership of `[at, len)` moves to `other`.
+        unsafe {
+            let last = (*first.as_ptr()).prev.expect("index is not the head");
+            (*last.as_ptr()).next = None;
+            (*first.as_ptr()).prev = None;
+
+            let other = LinkedList {
+                head: Some(first),
+                tail: self.tail,
+                size: self.size - at,
+                marker: PhantomData,
+            };
+
+            self.tail = Some(last);
+            self.size = at;
+            other
+        }
+    }
+
+    /// Moves every element of `other` to the end of `self` in O(1), leaving
+    /// `other` empty.
+    pub fn append(&mut self, other: &mut LinkedList<T>) {
+        let tail = match self.tail {
+            Some(tail) => tail,
+            None => return mem::swap(self, other),
+        };
+
+        if let Some(other_head) = other.head.take() {
+            // SAFETY: both nodes are live; the nodes of `other` now belong to
+            // `self` and `other` is reset to empty.
+            unsafe {
+                (*tail.as_ptr()).next = Some(other_head);
+                (*other_head.as_ptr()).prev = Some(tail);
+            }
+            self.tail = other.tail.take();
+            self.size += mem::replace(&mut other.size, 0);
+        }
+    }
+
+    /// Reverses the list in place by swapping every node's links.
+    pub fn reverse(&mut self) {
+        let mut cur = self.head;
+        while let Some(node) = cur {
+            // SAFETY: every node reachable from `head` is live and owned by
+            // this list.
+            unsafe {
+                let node = &mut *node.as_ptr();
+                mem::swap(&mut node.prev, &mut node.next);
+                cur = node.prev;
+            }
+        }
+        mem::swap(&mut self.head, &mut self.tail);
+    }
+
+    pub fn clear(&mut self) {
+        while self.pop_front().is_some() {}
+    }
+
+    fn node_at(&self, index: usize) -> Option<NonNull<Node<T>>> {
+        if index >= self.size {
             return None;
         }
 
-        self.size -= 1;
-        if self.size == 0 {
-            let node = self.head.take()?;
-            self.tail = None;
-            let value = Rc::try_unwrap(node).ok()?.into_inner().value;
-            return Some(value);
+        // SAFETY: `index < size`, so every step lands on a live node.
+        unsafe {
+            if index < self.size / 2 {
+                let mut cur = self.head?;
+                for _ in 0..index {
+                    cur = (*cur.as_ptr()).next?;
+                }
+                Some(cur)
+            } else {
+                let mut cur = self.tail?;
+                for _ in 0..self.size - 1 - index {
+                    cur = (*cur.as_ptr()).prev?;
+                }
+                Some(cur)
+            }
         }
+    }
 
-        let mut current = self.head.clone();
-        for _ in 0..self.size - 1 {
-            let next = current.as_ref()?.borrow().next.clone();
-            current = next;
+    /// Detaches `node` from the list and returns its value.
+    ///
+    /// # Safety
+    /// `node` must be a live node owned by this list.
+    unsafe fn unlink(&mut self, node: NonNull<Node<T>>) -> T {
+        // SAFETY: forwarded from the caller.
+        unsafe { self.unlink_node(node).value }
+    }
+
+    /// Detaches `node` from the list and hands back its allocation.
+    ///
+    /// # Safety
+    /// `node` must be a live node owned by this list.
+    unsafe fn unlink_node(&mut self, node: NonNull<Node<T>>) -> Box<Node<T>> {
+        // SAFETY: the caller guarantees `node` came from `Box::leak` in this
+        // list and has not been freed; its neighbours are live as well.
+        unsafe {
+            let mut node = Box::from_raw(node.as_ptr());
+            match node.prev {
+                Some(prev) => (*prev.as_ptr()).next = node.next,
+                None => self.head = node.next,
+            }
+            match node.next {
+                Some(next) => (*next.as_ptr()).prev = node.prev,
+                None => self.tail = node.prev,
+            }
+
+            self.size -= 1;
+            node.prev = None;
+            node.next = None;
+            node
         }
+    }
+
+    /// Links the chain `first..=last` of `count` nodes between the adjacent
+    /// nodes `prev` and `next`; `None` stands for the front or back.
+    ///
+    /// # Safety
+    /// `prev` and `next` must be adjacent live nodes of this list, and the
+    /// chain must be linked through `next` pointers and owned by nobody else.
+    unsafe fn link_between(
+        &mut self,
+        prev: Option<NonNull<Node<T>>>,
+        next: Option<NonNull<Node<T>>>,
+        first: NonNull<Node<T>>,
+        last: NonNull<Node<T>>,
+        count: usize,
+    ) {
+        // SAFETY: forwarded from the caller.
+        unsafe {
+            (*first.as_ptr()).prev = prev;
+            (*last.as_ptr()).next = next;
+            match prev {
+                Some(prev) => (*prev.as_ptr()).next = Some(first),
+                None => self.head = Some(first),
+            }
+            match next {
+                Some(next) => (*next.as_ptr()).prev = Some(last),
+                None => self.tail = Some(last),
+            }
+        }
+        self.size += count;
+    }
+
+    // Node-level push/pop so algorithms can move nodes between lists without
+    // reallocating them.
+    fn pop_front_node(&mut self) -> Option<Box<Node<T>>> {
+        // SAFETY: `head` is a live node owned by this list.
+        self.head.map(|node| unsafe { self.unlink_node(node) })
+    }
+
+    fn push_back_node(&mut self, mut node: Box<Node<T>>) {
+        node.next = None;
+        node.prev = self.tail;
+        let node = NonNull::from(Box::leak(node));
 
-        if let Some(node) = current {
-            let tail_node = node.borrow_mut().next.take()?;
-            self.tail = Some(node.clone());
-            let value = Rc::try_unwrap(tail_node).ok()?.into_inner().value;
-            return Some(value);
+        match self.tail {
+            // SAFETY: `tail` points to a live node owned by this list.
+            Some(tail) => unsafe { (*tail.as_ptr()).next = Some(node) },
+            None => self.head = Some(node),
         }
 
-        None
+        self.tail = Some(node);
+        self.size += 1;
     }
+}
 
+impl<T> LinkedList<T>
+where
+    T: Debug,
+{
     pub fn traversal(&self) {
-        let mut cur = self.head.clone();
-        while let Some(node) = cur {
-            println!("{:?}", node.borrow().value);
-            let next = node.borrow().next.clone();
-            cur = next;
+        for value in self.iter() {
+            println!("{:?}", value);
+        }
+    }
+}
+
+impl<T> Drop for LinkedList<T> {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+// Deep copy: the new list allocates its own nodes.
+impl<T> Clone for LinkedList<T>
+where
+    T: Clone,
+{
+    fn clone(&self) -> Self {
+        self.iter().cloned().collect()
+    }
+}
+
+impl<T> Debug for LinkedList<T>
+where
+    T: Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+/// Formats as `[1 <-> 2 <-> 3]`.
+impl<T> Display for LinkedList<T>
+where
+    T: Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[")?;
+        for (index, value) in self.iter().enumerate() {
+            if index > 0 {
+                write!(f, " <-> ")?;
+            }
+            write!(f, "{}", value)?;
         }
+        write!(f, "]")
     }
 }
 
+impl<T> PartialEq for LinkedList<T>
+where
+    T: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.size == other.size && self.iter().eq(other.iter())
+    }
+}
+
+impl<T> Eq for LinkedList<T> where T: Eq {}
+
 #[cfg(test)]
 mod test {
     use super::*;
+    use test_util::Rng;
+
+    // Walks the list both ways and checks the links agree with `size`.
+    fn to_vec(list: &LinkedList<i32>) -> Vec<i32> {
+        let mut forward = Vec::new();
+        let mut cur = list.head;
+        while let Some(node) = cur {
+            unsafe {
+                forward.push((*node.as_ptr()).value);
+                cur = (*node.as_ptr()).next;
+            }
+        }
+
+        let mut backward = Vec::new();
+        let mut cur = list.tail;
+        while let Some(node) = cur {
+            unsafe {
+                backward.push((*node.as_ptr()).value);
+                cur = (*node.as_ptr()).prev;
+            }
+        }
+        backward.reverse();
+
+        assert_eq!(forward, backward, "prev links disagree with next links");
+        assert_eq!(forward.len(), list.len(), "size is out of sync");
+        forward
+    }
+
+    fn std_to_vec(list: &std::collections::LinkedList<i32>) -> Vec<i32> {
+        list.iter().copied().collect()
+    }
 
     #[test]
     fn test() {
@@ -108,8 +473,308 @@ mod test {
 
         list.traversal();
 
-        println!("{:?}", list.pop());
-        println!("{:?}", list.pop());
-        println!("{:?}", list.pop());
+        assert_eq!(list.pop(), Some(2));
+        assert_eq!(list.pop(), Some(1));
+        assert_eq!(list.pop(), None);
+    }
+
+    #[test]
+    fn test_push_pop_both_ends() {
+        let mut list = LinkedList::new();
+        list.push_back(2);
+        list.push_front(1);
+        list.push_back(3);
+        assert_eq!(to_vec(&list), vec![1, 2, 3]);
+
+        assert_eq!(list.pop_front(), Some(1));
+        assert_eq!(list.pop_back(), Some(3));
+        assert_eq!(list.pop_front(), Some(2));
+        assert_eq!(list.pop_front(), None);
+        assert_eq!(list.pop_back(), None);
+        assert!(list.is_empty());
+        assert_eq!(to_vec(&list), vec![]);
+    }
+
+    #[test]
+    fn test_peek() {
+        let mut list = LinkedList::new();
+        assert_eq!(list.peek_front(), None);
+        assert_eq!(list.peek_back(), None);
+
+        list.push(1);
+        list.push(2);
+        assert_eq!(list.peek_front(), Some(&1));
+        assert_eq!(list.peek_back(), Some(&2));
+
+        *list.peek_front_mut().unwrap() = 10;
+        *list.peek_back_mut().unwrap() = 20;
+        assert_eq!(to_vec(&list), vec![10, 20]);
+    }
+
+    #[test]
+    fn test_get() {
+        let mut list = LinkedList::new();
+        for value in 0..5 {
+            list.push(value);
+        }
+
+        for index in 0..5 {
+            assert_eq!(list.get(index), Some(&(index as i32)));
+        }
+        assert_eq!(list.get(5), None);
+
+        *list.get_mut(3).unwrap() = 30;
+        assert_eq!(to_vec(&list), vec![0, 1, 2, 30, 4]);
+    }
+
+    #[test]
+    fn test_insert_and_remove() {
+        let mut list = LinkedList::new();
+        list.insert(0, 2);
+        list.insert(0, 0);
+        list.insert(1, 1);
+        list.insert(3, 4);
+        list.insert(3, 3);
+        assert_eq!(to_vec(&list), vec![0, 1, 2, 3, 4]);
+
+        assert_eq!(list.remove(2), Some(2));
+        assert_eq!(list.remove(0), Some(0));
+        assert_eq!(list.remove(2), Some(4));
+        assert_eq!(list.remove(2), None);
+        assert_eq!(to_vec(&list), vec![1, 3]);
+    }
+
+    #[test]
+    #[should_panic(expected = "insertion index (is 2) should be <= len (is 1)")]
+    fn test_insert_out_of_range() {
+        let mut list = LinkedList::new();
+        list.push(1);
+        list.insert(2, 2);
+    }
+
+    #[test]
+    fn test_split_off() {
+        let mut list = LinkedList::new();
+        for value in 0..5 {
+            list.push(value);
+        }
+
+        let tail = list.split_off(2);
+        assert_eq!(to_vec(&list), vec![0, 1]);
+        assert_eq!(to_vec(&tail), vec![2, 3, 4]);
+
+        let mut list = LinkedList::new();
+        list.push(1);
+        let all = list.split_off(0);
+        assert_eq!(to_vec(&list), vec![]);
+        assert_eq!(to_vec(&all), vec![1]);
+
+        let mut list = all;
+        let none = list.split_off(1);
+        assert_eq!(to_vec(&list), vec![1]);
+        assert_eq!(to_vec(&none), vec![]);
+    }
+
+    #[test]
+    fn test_append() {
+        let mut a = LinkedList::new();
+        let mut b = LinkedList::new();
+        a.push(1);
+        b.push(2);
+        b.push(3);
+
+        a.append(&mut b);
+        assert_eq!(to_vec(&a), vec![1, 2, 3]);
+        assert_eq!(to_vec(&b), vec![]);
+
+        let mut empty = LinkedList::new();
+        empty.append(&mut a);
+        assert_eq!(to_vec(&empty), vec![1, 2, 3]);
+        assert!(a.is_empty());
+
+        empty.append(&mut a);
+        assert_eq!(to_vec(&empty), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_reverse() {
+        let mut list = LinkedList::new();
+        list.reverse();
+        assert_eq!(to_vec(&list), vec![]);
+
+        for value in 0..4 {
+            list.push(value);
+        }
+        list.reverse();
+        assert_eq!(to_vec(&list), vec![3, 2, 1, 0]);
+
+        list.push_back(-1);
+        list.push_front(4);
+        assert_eq!(to_vec(&list), vec![4, 3, 2, 1, 0, -1]);
+    }
+
+    #[test]
+    fn test_clone_is_deep() {
+        let mut list = LinkedList::new();
+        list.push(1);
+        list.push(2);
+
+        let mut copy = list.clone();
+        *copy.get_mut(0).unwrap() = 10;
+        copy.push(3);
+
+        assert_eq!(to_vec(&list), vec![1, 2]);
+        assert_eq!(to_vec(&copy), vec![10, 2, 3]);
+    }
+
+    #[test]
+    fn test_pop_without_clone_or_debug() {
+        // neither Clone nor Debug
+        struct Token(u32);
+
+        let mut list = LinkedList::new();
+        list.push(Token(1));
+        list.push(Token(2));
+        list.push_front(Token(0));
+
+        assert_eq!(list.pop().map(|token| token.0), Some(2));
+        assert_eq!(list.pop_front().map(|token| token.0), Some(0));
+        assert_eq!(list.len(), 1);
+        assert_eq!(list.pop().map(|token| token.0), Some(1));
+        assert_eq!(list.pop().map(|token| token.0), None);
+        assert!(list.is_empty());
+    }
+
+    #[test]
+    fn test_pop_after_clone() {
+        // a clone used to share nodes with the original, so popping either
+        // one failed and left `size` out of sync
+        let mut list: LinkedList<i32> = (1..=3).collect();
+        let mut copy = list.clone();
+
+        assert_eq!(list.pop(), Some(3));
+        assert_eq!(copy.pop(), Some(3));
+        assert_eq!(copy.pop_front(), Some(1));
+
+        assert_eq!(to_vec(&list), vec![1, 2]);
+        assert_eq!(to_vec(&copy), vec![2]);
+    }
+
+    #[test]
+    fn test_display() {
+        let mut list = LinkedList::new();
+        assert_eq!(list.to_string(), "[]");
+
+        list.push(1);
+        assert_eq!(list.to_string(), "[1]");
+
+        list.push(2);
+        list.push(3);
+        assert_eq!(list.to_string(), "[1 <-> 2 <-> 3]");
+    }
+
+    #[test]
+    fn test_partial_eq() {
+        let a: LinkedList<i32> = (1..=3).collect();
+        let mut b: LinkedList<i32> = (1..=3).collect();
+        assert_eq!(a, b);
+
+        b.pop();
+        assert_ne!(a, b);
+        b.push(4);
+        assert_ne!(a, b);
+        assert_eq!(LinkedList::<i32>::new(), LinkedList::new());
+    }
+
+    #[test]
+    fn test_debug() {
+        let mut list = LinkedList::new();
+        list.push(1);
+        list.push(2);
+        assert_eq!(format!("{:?}", list), "[1, 2]");
+    }
+
+    #[test]
+    fn test_drops_every_value() {
+        use std::rc::Rc;
+
+        let counter = Rc::new(());
+        let mut list = LinkedList::new();
+        for _ in 0..10 {
+            list.push(counter.clone());
+        }
+        let tail = list.split_off(4);
+        list.remove(1);
+        assert_eq!(Rc::strong_count(&counter), 10);
+
+        drop(list);
+        drop(tail);
+        assert_eq!(Rc::strong_count(&counter), 1);
+    }
+
+    #[test]
+    fn test_matches_std_linked_list() {
+        use std::collections::LinkedList as StdList;
+
+        let mut rng = Rng(0x2545_f491_4f6c_dd1d);
+        for _ in 0..50 {
+            let mut list = LinkedList::new();
+            let mut expected = StdList::new();
+
+            for _ in 0..200 {
+                let value = rng.below(1000) as i32;
+                match rng.below(9) {
+                    0 => {
+                        list.push_back(value);
+                        expected.push_back(value);
+                    }
+                    1 => {
+                        list.push_front(value);
+                        expected.push_front(value);
+                    }
+                    2 => assert_eq!(list.pop_back(), expected.pop_back()),
+                    3 => assert_eq!(list.pop_front(), expected.pop_front()),
+                    4 => {
+                        let index = rng.below(expected.len() + 1);
+                        list.insert(index, value);
+                        let mut rest = expected.split_off(index);
+                        expected.push_back(value);
+                        expected.append(&mut rest);
+                    }
+                    5 => {
+                        let index = rng.below(expected.len() + 1);
+                        let removed = if index < expected.len() {
+                            let mut rest = expected.split_off(index);
+                            let removed = rest.pop_front();
+                            expected.append(&mut rest);
+                            removed
+                        } else {
+                            None
+                        };
+                        assert_eq!(list.remove(index), removed);
+                    }
+                    6 => {
+                        let index = rng.below(expected.len() + 1);
+                        assert_eq!(list.get(index), expected.iter().nth(index));
+                    }
+                    7 => {
+                        let at = rng.below(expected.len() + 1);
+                        let mut tail = list.split_off(at);
+                        let mut expected_tail = expected.split_off(at);
+                        assert_eq!(to_vec(&tail), std_to_vec(&expected_tail));
+                        list.append(&mut tail);
+                        expected.append(&mut expected_tail);
+                    }
+                    _ => {
+                        list.reverse();
+                        expected = expected.into_iter().rev().collect();
+                    }
+                }
+
+                assert_eq!(to_vec(&list), std_to_vec(&expected));
+                assert_eq!(list.peek_front(), expected.front());
+                assert_eq!(list.peek_back(), expected.back());
+            }
+        }
     }
 }
@@ -0,0 +1,27 @@
+//! Helpers shared by the randomized tests of the workspace crates. Only
+//! meant to be pulled in as a dev-dependency.
+
+/// xorshift64, enough to drive randomized tests without extra crates.
+///
+/// The seed must be non-zero, otherwise every output is zero.
+pub struct Rng(pub u64);
+
+impl Rng {
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    /// A value in `0..n`.
+    pub fn below(&mut self, n: usize) -> usize {
+        (self.next_u64() % n as u64) as usize
+    }
+
+    /// Up to `max_len` values, each in `0..max_value`.
+    pub fn vec(&mut self, max_len: usize, max_value: usize) -> Vec<i32> {
+        let len = self.below(max_len + 1);
+        (0..len).map(|_| self.below(max_value) as i32).collect()
+    }
+}